@@ -0,0 +1,219 @@
+use std::{fmt::Debug, time::Duration};
+
+use bitcoincore_rpc::jsonrpc::serde_json;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use zeroize::Zeroizing;
+
+/// Requests carry at most a few settings, so anything bigger is refused.
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A parsed http/1.1 request. The body may hold the mnemonic, so it is wiped on drop and redacted in debug output.
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    /// Names are lowercased.
+    pub headers: Vec<(String, String)>,
+    pub body: Zeroizing<Vec<u8>>,
+}
+
+impl Debug for HttpRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("path", &self.path)
+            .field(
+                "headers",
+                &self
+                    .headers
+                    .iter()
+                    .map(|(name, value)| match name.as_str() {
+                        // Holds the api token.
+                        "authorization" => (name.as_str(), "<redacted>"),
+                        _ => (name.as_str(), value.as_str()),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .field("body", &"<redacted>")
+            .finish()
+    }
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The media type of the body, without parameters like the charset.
+    pub fn is_json(&self) -> bool {
+        self.header("content-type").is_some_and(|content_type| {
+            content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .eq_ignore_ascii_case("application/json")
+        })
+    }
+
+    /// An empty body parses as the default value, so callers may leave out bodies with only optional fields.
+    pub fn json_body<T: DeserializeOwned + Default>(&self) -> Result<T, HttpResponse> {
+        if self.body.iter().all(|byte| byte.is_ascii_whitespace()) {
+            return Ok(T::default());
+        }
+        serde_json::from_slice(&self.body).map_err(|e| {
+            HttpResponse::error(400, &format!("The request body is not valid: {}.", e))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_string_pretty(value) {
+            Ok(body) => HttpResponse { status, body },
+            Err(e) => HttpResponse::error(500, &e.to_string()),
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        HttpResponse::json(status, &serde_json::json!({ "error": message }))
+    }
+
+    pub async fn write_to(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            reason_phrase(self.status),
+            self.body.len()
+        );
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(self.body.as_bytes()).await?;
+        stream.flush().await
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Reads one request. Errors come back as the response to send before closing the connection.
+pub async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest, HttpResponse> {
+    tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request_inner(stream))
+        .await
+        .unwrap_or_else(|_| {
+            Err(HttpResponse::error(
+                408,
+                "The request was not received in time.",
+            ))
+        })
+}
+
+async fn read_request_inner(stream: &mut TcpStream) -> Result<HttpRequest, HttpResponse> {
+    let mut buffer = Zeroizing::new(Vec::new());
+    let mut chunk = Zeroizing::new([0u8; 4096]);
+    let head_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+        if buffer.len() > MAX_HEAD_SIZE {
+            return Err(HttpResponse::error(413, "The request head is too large."));
+        }
+        let read = stream
+            .read(chunk.as_mut())
+            .await
+            .map_err(|e| HttpResponse::error(400, &e.to_string()))?;
+        if read == 0 {
+            return Err(HttpResponse::error(
+                400,
+                "The connection closed mid request.",
+            ));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err(HttpResponse::error(400, "The request line is not valid."));
+    };
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .map(|(_, value)| value.parse::<usize>())
+        .transpose()
+        .map_err(|_| HttpResponse::error(400, "The content length is not valid."))?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_SIZE {
+        return Err(HttpResponse::error(413, "The request body is too large."));
+    }
+    let mut body = Zeroizing::new(buffer[head_end + 4..].to_vec());
+    while body.len() < content_length {
+        let read = stream
+            .read(chunk.as_mut())
+            .await
+            .map_err(|e| HttpResponse::error(400, &e.to_string()))?;
+        if read == 0 {
+            return Err(HttpResponse::error(400, "The connection closed mid body."));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+    // Query strings are not used by any endpoint.
+    let path = target.split('?').next().unwrap_or_default().to_string();
+    Ok(HttpRequest {
+        method: method.to_string(),
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Starts a server-sent events stream. Events are then written with `write_event`.
+pub async fn write_event_stream_head(stream: &mut TcpStream) -> std::io::Result<()> {
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n")
+        .await?;
+    stream.flush().await
+}
+
+pub async fn write_event<T: Serialize>(
+    stream: &mut TcpStream,
+    event: &str,
+    value: &T,
+) -> std::io::Result<()> {
+    // Compact json keeps the whole event on one data line.
+    let data = serde_json::to_string(value).map_err(std::io::Error::other)?;
+    stream
+        .write_all(format!("event: {}\ndata: {}\n\n", event, data).as_bytes())
+        .await?;
+    stream.flush().await
+}
@@ -0,0 +1,249 @@
+use std::sync::Arc;
+
+use bitcoin::Network;
+use chrono::Utc;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+use zeroize::Zeroize;
+
+use crate::{
+    domain::{
+        dump_catalog::DumpFileEntry,
+        dump_integrity::{check_dump_integrity, DumpIntegrityProblem, DumpReadSummary},
+        finds::PathDescriptorFind,
+        results_export::{ResultsReport, ResultsSource},
+    },
+    gui_error::GuiError,
+    headless::FixedSettings,
+    uspk_set::UnspentScriptPubKeysSet,
+    view_elements::final_finds::FinalFinds,
+};
+
+/// Older jobs are dropped from the list beyond this many.
+const MAX_KEPT_JOBS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    CreateDump,
+    UseDump,
+    Populate,
+    Search,
+    Details,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Finished,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    pub id: u64,
+    pub kind: JobKind,
+    pub state: JobState,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    /// What the job found or why it failed.
+    pub message: Option<String>,
+}
+
+/// What a finished job hands back to the state.
+pub enum JobOutput {
+    Dump(DumpFileEntry),
+    Populated(UnspentScriptPubKeysSet, DumpReadSummary),
+    Searched(Vec<PathDescriptorFind>),
+    Detailed(Vec<FinalFinds>),
+}
+
+/// The progress of the pipeline as served by `/status` and `/events`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiStatus {
+    pub is_settings_fixed: bool,
+    pub network: Option<String>,
+    pub dump: Option<String>,
+    pub dump_height: Option<u64>,
+    pub is_uspk_set_ready: bool,
+    pub dump_integrity_problems: Vec<String>,
+    pub finds: Option<usize>,
+    pub final_finds: Option<usize>,
+    pub running_job: Option<Job>,
+    pub last_job: Option<Job>,
+}
+
+/// The api counterpart of the app state. Every step keeps its output here for the next one to use.
+#[derive(Default)]
+pub struct ApiState {
+    pub settings: Option<FixedSettings>,
+    /// Kept after a wipe, so the results can still be reported.
+    pub network: Option<Network>,
+    pub dump: Option<DumpFileEntry>,
+    pub uspk_set: Option<Arc<UnspentScriptPubKeysSet>>,
+    pub dump_integrity_problems: Vec<DumpIntegrityProblem>,
+    pub finds: Option<Vec<PathDescriptorFind>>,
+    pub final_finds: Option<Vec<FinalFinds>>,
+    pub jobs: Vec<Job>,
+    next_job_id: u64,
+    cancellation_token: CancellationToken,
+}
+
+impl ApiState {
+    pub fn running_job(&self) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.state == JobState::Running)
+    }
+
+    pub fn job(&self, id: u64) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    pub fn status(&self) -> ApiStatus {
+        ApiStatus {
+            is_settings_fixed: self.settings.is_some(),
+            network: self.network.map(|network| network.to_string()),
+            dump: self.dump.as_ref().map(|dump| dump.path_string()),
+            dump_height: self.dump.as_ref().and_then(|dump| dump.height()),
+            is_uspk_set_ready: self.uspk_set.is_some(),
+            dump_integrity_problems: self
+                .dump_integrity_problems
+                .iter()
+                .map(|problem| problem.to_string())
+                .collect(),
+            finds: self.finds.as_ref().map(|finds| finds.len()),
+            final_finds: self
+                .final_finds
+                .as_ref()
+                .map(|final_finds| final_finds.len()),
+            running_job: self.running_job().cloned(),
+            last_job: self
+                .jobs
+                .iter()
+                .rev()
+                .find(|job| job.state != JobState::Running)
+                .cloned(),
+        }
+    }
+
+    /// Replaces the settings, zeroizing the mnemonic and passphrase of the old ones.
+    pub fn set_settings(&mut self, settings: Option<FixedSettings>) {
+        if let Some(old_settings) = &mut self.settings {
+            old_settings.explorer_setting.zeroize();
+        }
+        if let Some(settings) = &settings {
+            self.network = Some(settings.network);
+        }
+        self.settings = settings;
+    }
+
+    /// Cancels a running search and drops the settings. Finds stay.
+    pub fn wipe_secrets(&mut self) {
+        if self
+            .running_job()
+            .is_some_and(|job| job.kind == JobKind::Search)
+        {
+            self.cancellation_token.cancel();
+        }
+        self.set_settings(None);
+    }
+
+    /// Records a new running job. The token cancels this job only.
+    pub fn begin_job(&mut self, kind: JobKind) -> (Job, CancellationToken) {
+        self.next_job_id += 1;
+        self.cancellation_token = CancellationToken::new();
+        let job = Job {
+            id: self.next_job_id,
+            kind,
+            state: JobState::Running,
+            started_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+            message: None,
+        };
+        self.jobs.push(job.clone());
+        if self.jobs.len() > MAX_KEPT_JOBS {
+            self.jobs.remove(0);
+        }
+        (job, self.cancellation_token.clone())
+    }
+
+    pub fn cancel_running_job(&mut self) -> bool {
+        if self.running_job().is_none() {
+            return false;
+        }
+        self.cancellation_token.cancel();
+        true
+    }
+
+    /// Stores the output of a job. `None` means the job was cancelled.
+    pub fn finish_job(&mut self, id: u64, outcome: Option<Result<JobOutput, GuiError>>) {
+        let (state, message) = match outcome {
+            None => (JobState::Cancelled, None),
+            Some(Err(e)) => (JobState::Failed, Some(format!("{:?}", e))),
+            Some(Ok(output)) => (JobState::Finished, Some(self.apply_output(output))),
+        };
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.state = state;
+            job.finished_at = Some(Utc::now().to_rfc3339());
+            job.message = message;
+        }
+    }
+
+    /// Keeps the output and drops whatever was built on what it replaces.
+    fn apply_output(&mut self, output: JobOutput) -> String {
+        match output {
+            JobOutput::Dump(dump) => {
+                let message = format!("Using the dump file {}.", dump.path_string());
+                if self.dump.as_ref() != Some(&dump) {
+                    self.uspk_set = None;
+                    self.dump_integrity_problems = vec![];
+                    self.finds = None;
+                    self.final_finds = None;
+                }
+                self.dump = Some(dump);
+                message
+            }
+            JobOutput::Populated(uspk_set, summary) => {
+                let expected_txoutset_hash = self
+                    .dump
+                    .as_ref()
+                    .and_then(|dump| dump.metadata.as_ref())
+                    .and_then(|metadata| metadata.comparable_txoutset_hash());
+                self.dump_integrity_problems =
+                    check_dump_integrity(&summary, expected_txoutset_hash);
+                let message = format!(
+                    "{} unspent scripts in the set, {} integrity problems.",
+                    uspk_set.get_immutable_inner_set().len(),
+                    self.dump_integrity_problems.len()
+                );
+                self.uspk_set = Some(Arc::new(uspk_set));
+                self.finds = None;
+                self.final_finds = None;
+                message
+            }
+            JobOutput::Searched(finds) => {
+                let message = format!("{} finds in the explored paths.", finds.len());
+                self.finds = Some(finds);
+                self.final_finds = None;
+                message
+            }
+            JobOutput::Detailed(final_finds) => {
+                let message = format!("Details of {} finds.", final_finds.len());
+                self.final_finds = Some(final_finds);
+                message
+            }
+        }
+    }
+
+    /// The same report the gui exports, with details once they are fetched.
+    pub fn results_report(&self) -> Option<ResultsReport> {
+        let network = self.network?;
+        let source = ResultsSource::new(network, self.dump.as_ref());
+        match (&self.final_finds, &self.finds) {
+            (Some(final_finds), _) => Some(ResultsReport::from_final_finds(final_finds, source)),
+            (None, Some(finds)) => Some(ResultsReport::from_finds(finds, network, source)),
+            (None, None) => None,
+        }
+    }
+}
@@ -0,0 +1,472 @@
+pub mod http;
+pub mod jobs;
+
+use std::{
+    future::Future,
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use bitceptron_retriever::{
+    client::BitcoincoreRpcClient, error::RetrieverError, explorer::Explorer,
+};
+use serde::Deserialize;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{
+    cli::CliArgs,
+    domain::profiles::is_valid_profile_name,
+    gui_error::GuiError,
+    headless::fix_settings,
+    inputs::secret::SecretString,
+    run_functions::{
+        check_for_dump_in_data_dir_or_create_dump_file, create_final_finds,
+        create_new_dump_in_data_dir, get_details_of_finds_from_bitcoincore,
+        get_details_of_finds_from_dump, populate_uspk_set, search_the_uspk_set,
+    },
+};
+
+use self::{
+    http::{read_request, write_event, write_event_stream_head, HttpRequest, HttpResponse},
+    jobs::{ApiState, JobKind, JobOutput},
+};
+
+const JOB_RUNNING_MESSAGE: &str = "A job is running. Cancel it or wait for it to finish.";
+const NO_SETTINGS_MESSAGE: &str = "There are no settings. Post them to /settings first.";
+const NO_DUMP_MESSAGE: &str = "There is no dump. Run a dump job first.";
+
+/// The body of `POST /settings`. Fields left out fall back to the command line flags.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SettingsRequest {
+    profile: Option<String>,
+    network: Option<String>,
+    rpc_url: Option<String>,
+    rpc_port: Option<String>,
+    cookie_path: Option<String>,
+    data_dir: Option<String>,
+    mnemonic: SecretString,
+    passphrase: SecretString,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DumpJobRequest {
+    new: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DetailsJobRequest {
+    from_dump: bool,
+}
+
+/// Runs the same steps as the gui, one job at a time, for scripts and dashboards.
+pub struct ApiServer {
+    args: CliArgs,
+    /// Random per run. Every request has to carry it as a bearer token.
+    token: String,
+    state: Mutex<ApiState>,
+    /// Ticks on every change of the state, for the event streams.
+    changes: watch::Sender<()>,
+}
+
+pub async fn run_api_server(args: CliArgs) -> Result<(), GuiError> {
+    if !args.api_bind.ip().is_loopback() {
+        warn!(
+            "The api is bound to {}, which is not a loopback address. Anyone who can reach it can run searches.",
+            args.api_bind
+        );
+    }
+    let listener = TcpListener::bind(args.api_bind)
+        .await
+        .map_err(RetrieverError::from)?;
+    let server = Arc::new(ApiServer::new(args)?);
+    info!("Api listening on http://{}.", server.args.api_bind);
+    // Stdout holds nothing else, so scripts can read the token off its first line.
+    println!("{}", server.token);
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(server.clone().handle_connection(stream));
+            }
+            Err(e) => warn!("Api connection not accepted: {}.", e),
+        }
+    }
+}
+
+impl ApiServer {
+    pub fn new(args: CliArgs) -> Result<Self, GuiError> {
+        let mut token_bytes = [0u8; 32];
+        getrandom::getrandom(&mut token_bytes)
+            .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?;
+        Ok(ApiServer {
+            args,
+            token: hex::encode(token_bytes),
+            state: Mutex::new(ApiState::default()),
+            changes: watch::channel(()).0,
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ApiState> {
+        self.state.lock().unwrap()
+    }
+
+    fn notify(&self) {
+        self.changes.send_replace(());
+    }
+
+    async fn handle_connection(self: Arc<Self>, mut stream: TcpStream) {
+        let request = read_request(&mut stream).await.and_then(|request| {
+            self.check_request(&request)?;
+            Ok(request)
+        });
+        let response = match request {
+            Ok(request) if request.method == "GET" && request.path == "/events" => {
+                if let Err(e) = self.stream_events(&mut stream).await {
+                    info!("Api event stream closed: {}.", e);
+                }
+                return;
+            }
+            Ok(request) => self.route(&request),
+            Err(response) => response,
+        };
+        if let Err(e) = response.write_to(&mut stream).await {
+            warn!("Api response not sent: {}.", e);
+        }
+    }
+
+    /// Refuses requests without the token, requests for another host, which is what a rebound dns name sends, and
+    /// posts that are not json, which browsers can not send cross origin without asking first.
+    fn check_request(&self, request: &HttpRequest) -> Result<(), HttpResponse> {
+        if !request
+            .header("host")
+            .is_some_and(|host| self.is_bound_host(host))
+        {
+            return Err(HttpResponse::error(
+                400,
+                "The host is not the address the api is bound to.",
+            ));
+        }
+        let is_token_valid = request
+            .header("authorization")
+            .and_then(|authorization| authorization.strip_prefix("Bearer "))
+            .is_some_and(|token| is_same_token(token.trim(), &self.token));
+        if !is_token_valid {
+            return Err(HttpResponse::error(
+                401,
+                "The request does not carry the api token.",
+            ));
+        }
+        if request.method == "POST" && !request.is_json() {
+            return Err(HttpResponse::error(
+                415,
+                "Posts need the content type application/json.",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The bind address itself, `localhost` when bound to loopback, or any address when bound to all of them.
+    fn is_bound_host(&self, host: &str) -> bool {
+        let bind = self.args.api_bind;
+        if let Ok(host) = SocketAddr::from_str(host) {
+            return host.port() == bind.port()
+                && (host.ip() == bind.ip() || bind.ip().is_unspecified());
+        }
+        bind.ip().is_loopback() && host.eq_ignore_ascii_case(&format!("localhost:{}", bind.port()))
+    }
+
+    /// Sends the status on connect and again on every change, until the client goes away.
+    async fn stream_events(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let mut changes = self.changes.subscribe();
+        write_event_stream_head(stream).await?;
+        loop {
+            let status = self.lock().status();
+            write_event(stream, "status", &status).await?;
+            if changes.changed().await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn route(self: &Arc<Self>, request: &HttpRequest) -> HttpResponse {
+        let path = request.path.trim_end_matches('/');
+        match (request.method.as_str(), path) {
+            ("GET", "/status") => HttpResponse::json(200, &self.lock().status()),
+            ("POST", "/settings") => self.set_settings(request),
+            ("POST", "/wipe") => {
+                self.lock().wipe_secrets();
+                self.notify();
+                HttpResponse::json(200, &self.lock().status())
+            }
+            ("GET", "/jobs") => HttpResponse::json(200, &self.lock().jobs),
+            ("POST", "/jobs/dump") => self.start_dump_job(request),
+            ("POST", "/jobs/populate") => self.start_populate_job(),
+            ("POST", "/jobs/search") => self.start_search_job(),
+            ("POST", "/jobs/details") => self.start_details_job(request),
+            ("POST", "/jobs/cancel") => {
+                if !self.lock().cancel_running_job() {
+                    return HttpResponse::error(409, "No job is running.");
+                }
+                HttpResponse::json(202, &self.lock().status())
+            }
+            ("GET", "/results") => match self.lock().results_report() {
+                Some(report) => HttpResponse::json(200, &report),
+                None => HttpResponse::error(409, "There are no results yet. Run a search first."),
+            },
+            ("GET", _) => match path
+                .strip_prefix("/jobs/")
+                .and_then(|id| id.parse::<u64>().ok())
+                .and_then(|id| self.lock().job(id).cloned())
+            {
+                Some(job) => HttpResponse::json(200, &job),
+                None => HttpResponse::error(404, "Not found."),
+            },
+            _ => HttpResponse::error(404, "Not found."),
+        }
+    }
+
+    fn set_settings(&self, request: &HttpRequest) -> HttpResponse {
+        let settings_request: SettingsRequest = match request.json_body() {
+            Ok(settings_request) => settings_request,
+            Err(response) => return response,
+        };
+        if settings_request
+            .profile
+            .as_deref()
+            .is_some_and(|profile| !is_valid_profile_name(profile))
+        {
+            return HttpResponse::error(400, "The profile name is not valid.");
+        }
+        let network = match settings_request
+            .network
+            .as_deref()
+            .map(bitcoin::Network::from_str)
+        {
+            Some(Ok(network)) => Some(network),
+            Some(Err(_)) => return HttpResponse::error(400, "The network is not valid."),
+            None => self.args.network,
+        };
+        let args = CliArgs {
+            profile: settings_request.profile.or(self.args.profile.clone()),
+            network,
+            rpc_url: settings_request.rpc_url.or(self.args.rpc_url.clone()),
+            rpc_port: settings_request.rpc_port.or(self.args.rpc_port.clone()),
+            cookie_path: settings_request
+                .cookie_path
+                .or(self.args.cookie_path.clone()),
+            data_dir: settings_request.data_dir.or(self.args.data_dir.clone()),
+            ..self.args.clone()
+        };
+        let settings = match fix_settings(
+            &args,
+            settings_request.mnemonic,
+            settings_request.passphrase,
+        ) {
+            Ok(settings) => settings,
+            Err(problem) => return HttpResponse::error(400, &problem.to_string()),
+        };
+        let mut state = self.lock();
+        if state.running_job().is_some() {
+            return HttpResponse::error(409, JOB_RUNNING_MESSAGE);
+        }
+        state.set_settings(Some(settings));
+        let response = HttpResponse::json(200, &state.status());
+        drop(state);
+        self.notify();
+        response
+    }
+
+    /// Records the job and runs it in the background. Cancelling the job drops its future, and the work gets the
+    /// job's token to stop whatever it runs outside that future.
+    fn spawn_job<W, F>(
+        self: &Arc<Self>,
+        mut state: MutexGuard<'_, ApiState>,
+        kind: JobKind,
+        work: W,
+    ) -> HttpResponse
+    where
+        W: FnOnce(CancellationToken) -> F,
+        F: Future<Output = Result<JobOutput, GuiError>> + Send + 'static,
+    {
+        let (job, cancellation_token) = state.begin_job(kind);
+        drop(state);
+        self.notify();
+        let server = self.clone();
+        let id = job.id;
+        let work = work(cancellation_token.clone());
+        tokio::spawn(async move {
+            let outcome = tokio::select! {
+                // Checked first, so a cancelled job counts as cancelled even if its work returns at the same time.
+                biased;
+                _ = cancellation_token.cancelled() => None,
+                outcome = work => Some(outcome),
+            };
+            server.lock().finish_job(id, outcome);
+            server.notify();
+        });
+        HttpResponse::json(202, &job)
+    }
+
+    /// The state, if no job is running.
+    fn lock_for_new_job(&self) -> Result<MutexGuard<'_, ApiState>, HttpResponse> {
+        let state = self.lock();
+        if state.running_job().is_some() {
+            return Err(HttpResponse::error(409, JOB_RUNNING_MESSAGE));
+        }
+        Ok(state)
+    }
+
+    fn start_dump_job(self: &Arc<Self>, request: &HttpRequest) -> HttpResponse {
+        let dump_request: DumpJobRequest = match request.json_body() {
+            Ok(dump_request) => dump_request,
+            Err(response) => return response,
+        };
+        let state = match self.lock_for_new_job() {
+            Ok(state) => state,
+            Err(response) => return response,
+        };
+        let Some(settings) = &state.settings else {
+            return HttpResponse::error(409, NO_SETTINGS_MESSAGE);
+        };
+        let client_setting = settings.client_setting.clone();
+        let data_dir = settings.data_dir.clone();
+        let kind = if dump_request.new {
+            JobKind::CreateDump
+        } else {
+            JobKind::UseDump
+        };
+        self.spawn_job(state, kind, |_| async move {
+            let client = BitcoincoreRpcClient::new(client_setting.clone()).await?;
+            let dump = if dump_request.new {
+                create_new_dump_in_data_dir(data_dir, client, client_setting).await?
+            } else {
+                check_for_dump_in_data_dir_or_create_dump_file(data_dir, client, client_setting)
+                    .await?
+            };
+            Ok(JobOutput::Dump(dump))
+        })
+    }
+
+    fn start_populate_job(self: &Arc<Self>) -> HttpResponse {
+        let state = match self.lock_for_new_job() {
+            Ok(state) => state,
+            Err(response) => return response,
+        };
+        let Some(settings) = &state.settings else {
+            return HttpResponse::error(409, NO_SETTINGS_MESSAGE);
+        };
+        let Some(dump) = &state.dump else {
+            return HttpResponse::error(409, NO_DUMP_MESSAGE);
+        };
+        let dump_file_path = dump.path.clone();
+        let verify_dump_hash = settings.verify_dump_hash;
+        self.spawn_job(state, JobKind::Populate, |cancellation_token| async move {
+            // The dump is read on a blocking thread, which only stops once the token is cancelled.
+            let (uspk_set, summary) =
+                populate_uspk_set(dump_file_path, verify_dump_hash, cancellation_token).await?;
+            Ok(JobOutput::Populated(uspk_set, summary))
+        })
+    }
+
+    fn start_search_job(self: &Arc<Self>) -> HttpResponse {
+        let state = match self.lock_for_new_job() {
+            Ok(state) => state,
+            Err(response) => return response,
+        };
+        let Some(settings) = &state.settings else {
+            return HttpResponse::error(409, NO_SETTINGS_MESSAGE);
+        };
+        let Some(uspk_set) = &state.uspk_set else {
+            return HttpResponse::error(
+                409,
+                "The utxo set is not populated. Run a populate job first.",
+            );
+        };
+        // The gui refuses to search such a dump too.
+        if !state.dump_integrity_problems.is_empty() {
+            let problems: Vec<String> = state
+                .dump_integrity_problems
+                .iter()
+                .map(|problem| problem.to_string())
+                .collect();
+            return HttpResponse::error(
+                409,
+                &format!(
+                    "The dump failed its integrity check, so it is not searched: {}",
+                    problems.join(" ")
+                ),
+            );
+        }
+        let selected_descriptors = settings.selected_descriptors.clone();
+        let explorer_setting = settings.explorer_setting.clone();
+        let uspk_set = uspk_set.clone();
+        self.spawn_job(state, JobKind::Search, |cancellation_token| async move {
+            let explorer = Arc::new(Explorer::new(explorer_setting)?);
+            let finds =
+                search_the_uspk_set(selected_descriptors, uspk_set, explorer, cancellation_token)
+                    .await;
+            Ok(JobOutput::Searched(finds))
+        })
+    }
+
+    fn start_details_job(self: &Arc<Self>, request: &HttpRequest) -> HttpResponse {
+        let details_request: DetailsJobRequest = match request.json_body() {
+            Ok(details_request) => details_request,
+            Err(response) => return response,
+        };
+        let state = match self.lock_for_new_job() {
+            Ok(state) => state,
+            Err(response) => return response,
+        };
+        let Some(settings) = &state.settings else {
+            return HttpResponse::error(409, NO_SETTINGS_MESSAGE);
+        };
+        let Some(finds) = &state.finds else {
+            return HttpResponse::error(409, "There are no finds. Run a search job first.");
+        };
+        let dump_file_path = match (&state.dump, details_request.from_dump) {
+            (Some(dump), true) => Some(dump.path.clone()),
+            (None, true) => return HttpResponse::error(409, NO_DUMP_MESSAGE),
+            (_, false) => None,
+        };
+        let finds = finds.clone();
+        let client_setting = settings.client_setting.clone();
+        let network = settings.network;
+        self.spawn_job(state, JobKind::Details, |_| async move {
+            if finds.is_empty() {
+                return Ok(JobOutput::Detailed(vec![]));
+            }
+            let detailed_finds = match dump_file_path {
+                Some(dump_file_path) => {
+                    get_details_of_finds_from_dump(finds, dump_file_path).await?
+                }
+                None => {
+                    let client = BitcoincoreRpcClient::new(client_setting).await?;
+                    get_details_of_finds_from_bitcoincore(finds, client).await?
+                }
+            };
+            Ok(JobOutput::Detailed(create_final_finds(
+                detailed_finds,
+                network,
+            )?))
+        })
+    }
+}
+
+/// Compares every byte, so the time taken does not tell how much of the token was right.
+fn is_same_token(token: &str, expected: &str) -> bool {
+    token.len() == expected.len()
+        && token
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
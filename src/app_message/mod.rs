@@ -1,12 +1,28 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
-use bitceptron_retriever::{
-    client::BitcoincoreRpcClient,
-    error::RetrieverError,
-    path_pairs::{PathDescriptorPair, PathScanResultDescriptorTrio},
-};
+use bitceptron_retriever::client::BitcoincoreRpcClient;
+use bitcoin::Txid;
 
-use crate::uspk_set::UnspentScriptPubKeysSet;
+use crate::{
+    domain::{
+        broadcast::{BroadcastStatus, MempoolAcceptance},
+        fee_estimates::FeeEstimates,
+        core_wallet::CoreWalletImportReport,
+        descriptor_export::ImportDescriptorsVariant,
+        dump_catalog::DumpFileEntry, dump_integrity::DumpReadSummary,
+        dump_preflight::{DumpIntent, DumpPreflightReport},
+        finds::{DetailedFind, PathDescriptorFind},
+        profiles::SettingsProfile,
+        results_export::ExportFormat,
+        results_table::{ResultsSortColumn, ScriptTypeFilter},
+        snapshot_header::InspectedSnapshot,
+        split_workflow::{OfflineDerivationSummary, OnlineMatchReport},
+        sweep::{PreparedSweep, SignedSweep},
+    },
+    gui_error::GuiError,
+    inputs::secret::SecretString,
+    uspk_set::UnspentScriptPubKeysSet,
+};
 
 use self::{
     setting_input_fixed::SettingInputFixedMessage, setting_input_in_gui::SettingInputInGuiMessage,
@@ -24,17 +40,117 @@ pub enum AppMessage {
     ClientCreatedForDumpFileSoPrepareDumpFile(BitcoincoreRpcClient),
     CreateClientForNewDumpFileAndThenCreate,
     ClientCreatedForNewFileSoCreateDumpFile(BitcoincoreRpcClient),
-    DumpFilePrepared,
+    DumpPreflightReady(DumpIntent, BitcoincoreRpcClient, DumpPreflightReport),
+    DumpPreflightConfirmed,
+    DumpPreflightDismissed,
+    LatestDumpForNodeLookedUp(BitcoincoreRpcClient, Option<DumpFileEntry>),
+    DumpFilePrepared(DumpFileEntry),
+    RefreshDumpFiles,
+    DumpFilesListed(Vec<DumpFileEntry>),
+    DumpFileSelected(PathBuf),
+    DeleteDumpFileRequested(DumpFileEntry),
+    DeleteDumpFileConfirmed,
+    DeleteDumpFileDismissed,
+    DumpFileDeleted(PathBuf),
+    SnapshotImportPathChanged(String),
+    InspectSnapshotFile,
+    SnapshotInspected(InspectedSnapshot),
+    UseInspectedSnapshot,
+    DismissInspectedSnapshot,
+    NodeTipHeightReady(u64),
     PopulateUtxoDB,
     StopPopulatingUtxoDB,
     Search,
     StopSearch,
-    SearchResultPrepared(Vec<PathDescriptorPair>),
+    SearchResultPrepared(Vec<PathDescriptorFind>),
     // RetrieverCreated(Retriever),
-    SetPopulated(UnspentScriptPubKeysSet),
+    SetPopulated(UnspentScriptPubKeysSet, DumpReadSummary),
     CreateClientForGettingDetailsAndThenGet,
     ClientCreatedForGettingDetailsSoGetDetails(BitcoincoreRpcClient),
-    DetailsReady(Option<Vec<PathScanResultDescriptorTrio>>),
-    Error(Arc<RetrieverError>),
+    GetDetailsFromDump,
+    DeriveScriptsOffline,
+    ScriptsDerivedOffline(OfflineDerivationSummary),
+    DerivedScriptsFilePathChanged(String),
+    MatchDerivedScriptsOnline,
+    DerivedScriptsMatchedOnline(OnlineMatchReport),
+    DetailsReady(Option<Vec<DetailedFind>>),
+    ToggleFindExpanded(u64),
+    ToggleAddressQrCode(u64),
+    CopyToClipboard(String),
+    OpenMessageSigning(u64),
+    CloseMessageSigning,
+    MessageToSignChanged(String),
+    SignMessage,
+    VerifyAddressChanged(String),
+    VerifyMessageChanged(String),
+    VerifySignatureChanged(String),
+    VerifyMessageSignature,
+    ExportResults(ExportFormat),
+    ResultsExported(PathBuf),
+    ExportImportDescriptors(ImportDescriptorsVariant),
+    SpendableDescriptorsExportConfirmed,
+    SpendableDescriptorsExportDismissed,
+    ShowWifKeys,
+    WifKeysConfirmationChanged(String),
+    WifKeysConfirmed,
+    WifKeysDismissed,
+    CopyWifKeys,
+    WipeWifKeys,
+    ProfilesListed(Vec<String>),
+    ProfileSelected(String),
+    LoadProfile,
+    ProfileLoaded(String, SettingsProfile),
+    ProfileNameChanged(String),
+    SaveProfile,
+    ProfileSaved(PathBuf),
+    ToggleMnemonicVisibility,
+    ToggleMnemonicWordGrid,
+    MnemonicWordCountChanged(usize),
+    MnemonicWordChanged(usize, SecretString),
+    MnemonicWordSubmitted(usize),
+    MnemonicSuggestionChosen(usize, &'static str),
+    TogglePassphraseVisibility,
+    WipeSecrets,
+    WipeSecretsAfterSearchChanged(bool),
+    WipeSecretsAfterIdleChanged(String),
+    CheckIdleWipe,
+    CoreWalletNameChanged(String),
+    CoreWalletSpendableChanged(bool),
+    SendFindsToCoreWallet,
+    CoreWalletImportFinished(CoreWalletImportReport),
+    SweepDestinationChanged(String),
+    SweepFeeRateChanged(String),
+    GetFeeEstimates,
+    FeeEstimatesReady(FeeEstimates),
+    BuildSweepPsbt,
+    SweepPsbtReady(PreparedSweep),
+    SignSweep,
+    SweepSigned(SignedSweep),
+    TestSweepMempoolAcceptance,
+    SweepMempoolAcceptanceTested(MempoolAcceptance),
+    BroadcastSweep,
+    SweepBroadcastConfirmed,
+    SweepBroadcastDismissed,
+    SweepBroadcast(Txid),
+    CheckSweepBroadcastStatus,
+    SweepBroadcastStatusChecked(BroadcastStatus),
+    ResultsSortRequested(ResultsSortColumn),
+    ResultsScriptTypeFilterChanged(ScriptTypeFilter),
+    ResultsMinAmountChanged(String),
+    OperationFailed(Operation, Arc<GuiError>),
+    Error(Arc<GuiError>),
     None,
 }
+
+/// The operations that show as in progress in the gui, so a failure clears only the flag of its own operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Populating,
+    Search,
+    OnlineMatch,
+    GettingDetails,
+    CoreWalletImport,
+    GettingFeeEstimates,
+    Sweep,
+    SweepBroadcastStatusCheck,
+}
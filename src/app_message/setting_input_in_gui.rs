@@ -1,4 +1,6 @@
 
+use crate::inputs::secret::SecretString;
+
 #[derive(Debug, Clone)]
 pub enum SettingInputInGuiMessage {
     BitcoincoreUrlChanged(String),
@@ -17,6 +19,8 @@ pub enum SettingInputInGuiMessage {
     P2shwpkhInclusionChanged(bool),
     P2trInclusionChanged(bool),
     DataDirChanged(String),
-    MnemonicChanged(String),
-    PassphraseChanged(String),
+    VerifyDumpHashChanged(bool),
+    StaleDumpThresholdChanged(String),
+    MnemonicChanged(SecretString),
+    PassphraseChanged(SecretString),
 }
\ No newline at end of file
@@ -0,0 +1,259 @@
+use std::{io::Read, net::SocketAddr, path::PathBuf, str::FromStr};
+
+use bitceptron_retriever::error::RetrieverError;
+use clap::{crate_version, Arg, ArgAction, ArgMatches, Command};
+use zeroize::Zeroizing;
+
+use crate::{
+    domain::profiles::{profiles_dir, SettingsProfile},
+    inputs::{
+        bitcoincore_client::BitcoincoreClientInput, explorer::ExplorerInput,
+        retriever_specific::RetrieverSpecificInput, secret::SecretString,
+    },
+};
+
+/// The command line arguments. In the gui they only prefill the inputs, which still have to be fixed.
+#[derive(Debug, Clone)]
+pub struct CliArgs {
+    pub profile: Option<String>,
+    pub network: Option<bitcoin::Network>,
+    pub rpc_url: Option<String>,
+    pub rpc_port: Option<String>,
+    pub cookie_path: Option<String>,
+    pub data_dir: Option<String>,
+    pub headless: bool,
+    /// The file descriptor the mnemonic, and optionally the passphrase on a second line, are read from.
+    pub mnemonic_fd: i32,
+    pub new_dump: bool,
+    pub details_from_dump: bool,
+    pub output: Option<PathBuf>,
+    pub api: bool,
+    pub api_bind: SocketAddr,
+}
+
+/// Where the api listens unless `--api-bind` says otherwise. Loopback only, so other machines can not reach it.
+pub const DEFAULT_API_BIND: &str = "127.0.0.1:8339";
+
+/// The mnemonic and passphrase lines fit in far less, a longer input is refused rather than buffered.
+const MAX_SECRETS_INPUT_LEN: usize = 4096;
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        CliArgs {
+            profile: None,
+            network: None,
+            rpc_url: None,
+            rpc_port: None,
+            cookie_path: None,
+            data_dir: None,
+            headless: false,
+            mnemonic_fd: 0,
+            new_dump: false,
+            details_from_dump: false,
+            output: None,
+            api: false,
+            api_bind: SocketAddr::from_str(DEFAULT_API_BIND).unwrap(),
+        }
+    }
+}
+
+impl CliArgs {
+    pub fn command() -> Command {
+        Command::new("bitceptron-retriever-gui")
+            .version(crate_version!())
+            .about("Finds the unspent outputs of a mnemonic by exploring derivation paths over a dump of the utxo set.")
+            .arg(
+                Arg::new("profile")
+                    .long("profile")
+                    .value_name("NAME")
+                    .help("Settings profile to start from, other flags override its values"),
+            )
+            .arg(
+                Arg::new("network")
+                    .long("network")
+                    .value_parser(["bitcoin", "testnet", "signet", "regtest"]),
+            )
+            .arg(Arg::new("rpc-url").long("rpc-url").value_name("URL"))
+            .arg(Arg::new("rpc-port").long("rpc-port").value_name("PORT"))
+            .arg(Arg::new("cookie-path").long("cookie-path").value_name("PATH"))
+            .arg(Arg::new("data-dir").long("data-dir").value_name("PATH"))
+            .arg(
+                Arg::new("headless")
+                    .long("headless")
+                    .action(ArgAction::SetTrue)
+                    .help("Dump, populate, search and get details without a window, then print the results as json"),
+            )
+            .arg(
+                Arg::new("mnemonic-fd")
+                    .long("mnemonic-fd")
+                    .value_name("FD")
+                    .value_parser(clap::value_parser!(i32))
+                    .default_value("0")
+                    .help("Headless only. File descriptor to read the mnemonic from, and the passphrase from its second line"),
+            )
+            .arg(
+                Arg::new("new-dump")
+                    .long("new-dump")
+                    .action(ArgAction::SetTrue)
+                    .help("Headless only. Create a new dump even if one of the node's chain is in the data dir"),
+            )
+            .arg(
+                Arg::new("details-from-dump")
+                    .long("details-from-dump")
+                    .action(ArgAction::SetTrue)
+                    .help("Headless only. Read the details of the finds from the dump instead of asking bitcoincore"),
+            )
+            .arg(
+                Arg::new("output")
+                    .long("output")
+                    .value_name("PATH")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Headless only. Write the json results to this file instead of stdout"),
+            )
+            .arg(
+                Arg::new("api")
+                    .long("api")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with("headless")
+                    .help("Serve the http api instead of opening a window"),
+            )
+            .arg(
+                Arg::new("api-bind")
+                    .long("api-bind")
+                    .value_name("ADDRESS")
+                    .value_parser(clap::value_parser!(SocketAddr))
+                    .default_value(DEFAULT_API_BIND)
+                    .help("Api only. Address and port to listen on"),
+            )
+    }
+
+    pub fn parse() -> Self {
+        CliArgs::from_matches(&CliArgs::command().get_matches())
+    }
+
+    pub fn from_matches(matches: &ArgMatches) -> Self {
+        let string_of = |id: &str| matches.get_one::<String>(id).cloned();
+        CliArgs {
+            profile: string_of("profile"),
+            network: string_of("network")
+                .and_then(|network| bitcoin::Network::from_str(&network).ok()),
+            rpc_url: string_of("rpc-url"),
+            rpc_port: string_of("rpc-port"),
+            cookie_path: string_of("cookie-path"),
+            data_dir: string_of("data-dir"),
+            headless: matches.get_flag("headless"),
+            mnemonic_fd: matches.get_one::<i32>("mnemonic-fd").copied().unwrap_or(0),
+            new_dump: matches.get_flag("new-dump"),
+            details_from_dump: matches.get_flag("details-from-dump"),
+            output: matches.get_one::<PathBuf>("output").cloned(),
+            api: matches.get_flag("api"),
+            api_bind: matches
+                .get_one::<SocketAddr>("api-bind")
+                .copied()
+                .unwrap_or(SocketAddr::from_str(DEFAULT_API_BIND).unwrap()),
+        }
+    }
+
+    /// Fills the inputs from the profile, if any, then from the flags.
+    pub fn prefill_inputs(
+        &self,
+        bitcoincore_client_input: &mut BitcoincoreClientInput,
+        explorer_input: &mut ExplorerInput,
+        retriever_specific_input: &mut RetrieverSpecificInput,
+    ) -> Result<(), RetrieverError> {
+        if let Some(profile_name) = &self.profile {
+            let profiles_dir =
+                profiles_dir().ok_or(RetrieverError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "no config dir found for the profiles",
+                )))?;
+            SettingsProfile::load(&profiles_dir, profile_name)?.apply_to(
+                bitcoincore_client_input,
+                explorer_input,
+                retriever_specific_input,
+            );
+        }
+        if let Some(network) = self.network {
+            explorer_input.set_network_from_gui_input(network);
+        }
+        if let Some(rpc_url) = &self.rpc_url {
+            bitcoincore_client_input.set_url_from_gui_input(rpc_url.clone());
+        }
+        if let Some(rpc_port) = &self.rpc_port {
+            bitcoincore_client_input.set_rpc_port_from_gui_input(rpc_port.clone());
+        }
+        if let Some(cookie_path) = &self.cookie_path {
+            bitcoincore_client_input.set_cookie_path_from_gui_input(cookie_path.clone());
+        }
+        if let Some(data_dir) = &self.data_dir {
+            retriever_specific_input.set_data_dir_from_gui_input(data_dir.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Reads the mnemonic from the first line of a file descriptor and the passphrase, if given, from the second.
+pub fn read_secrets_from_fd(fd: i32) -> Result<(SecretString, SecretString), RetrieverError> {
+    if fd < 0 || fd == 1 || fd == 2 {
+        return Err(RetrieverError::IoError(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the mnemonic can not be read from stdout, stderr or a negative file descriptor",
+        )));
+    }
+    let content = if fd == 0 {
+        read_secrets_input(std::io::stdin().lock())?
+    } else {
+        read_secrets_input(open_fd(fd)?)?
+    };
+    let content = std::str::from_utf8(&content)
+        .map_err(|e| RetrieverError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+    let mut lines = content.lines();
+    let mnemonic = SecretString::new(lines.next().unwrap_or_default().trim().to_string());
+    let passphrase = SecretString::new(lines.next().unwrap_or_default().to_string());
+    Ok((mnemonic, passphrase))
+}
+
+/// Reads the whole input into a buffer allocated once, so the secrets are never copied into a reallocation that
+/// is freed without being wiped.
+fn read_secrets_input(mut reader: impl Read) -> Result<Zeroizing<Vec<u8>>, RetrieverError> {
+    let mut buffer = Zeroizing::new(vec![0u8; MAX_SECRETS_INPUT_LEN]);
+    let mut len = 0;
+    loop {
+        if len == buffer.len() {
+            return Err(RetrieverError::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("the mnemonic input is longer than {} bytes", MAX_SECRETS_INPUT_LEN),
+            )));
+        }
+        match reader.read(&mut buffer[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    buffer.truncate(len);
+    Ok(buffer)
+}
+
+#[cfg(unix)]
+fn open_fd(fd: i32) -> Result<std::fs::File, RetrieverError> {
+    use std::os::fd::{FromRawFd, OwnedFd};
+    // Read through a duplicate, so the descriptor itself is never closed here.
+    // SAFETY: fcntl only looks the descriptor up, one that is not open fails the dup with EBADF.
+    let duplicate = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+    if duplicate < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // SAFETY: `duplicate` is a descriptor fcntl has just opened for this process and nothing else owns it.
+    let duplicate = unsafe { OwnedFd::from_raw_fd(duplicate) };
+    Ok(std::fs::File::from(duplicate))
+}
+
+#[cfg(not(unix))]
+fn open_fd(_fd: i32) -> Result<std::fs::File, RetrieverError> {
+    Err(RetrieverError::IoError(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "reading the mnemonic from a file descriptor other than stdin is only supported on unix",
+    )))
+}
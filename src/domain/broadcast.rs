@@ -0,0 +1,99 @@
+use std::{fmt::Display, time::Duration};
+
+use bitcoin::{Amount, Txid};
+use bitcoincore_rpc::jsonrpc::serde_json::Value;
+use num_format::{Locale, ToFormattedString};
+
+/// How often a broadcast sweep is looked up on the node until it confirms.
+pub const CONFIRMATION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The verdict of bitcoincore's `testmempoolaccept` on a signed transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolAcceptance {
+    pub txid: String,
+    pub allowed: bool,
+    pub reject_reason: Option<String>,
+    pub vsize: Option<u64>,
+    pub fee_in_sat: Option<u64>,
+}
+
+impl MempoolAcceptance {
+    /// Reads the single entry of a `testmempoolaccept` response.
+    pub fn from_testmempoolaccept(response: &Value) -> Option<Self> {
+        let entry = response.as_array()?.first()?;
+        Some(MempoolAcceptance {
+            txid: entry["txid"].as_str()?.to_string(),
+            allowed: entry["allowed"].as_bool()?,
+            reject_reason: entry["reject-reason"]
+                .as_str()
+                .map(|reason| reason.to_string()),
+            vsize: entry["vsize"].as_u64(),
+            fee_in_sat: entry["fees"]["base"]
+                .as_f64()
+                .and_then(|btc| Amount::from_btc(btc).ok())
+                .map(|amount| amount.to_sat()),
+        })
+    }
+}
+
+impl Display for MempoolAcceptance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.allowed {
+            return write!(
+                f,
+                "The node rejects the transaction: {}",
+                self.reject_reason.as_deref().unwrap_or("no reason given")
+            );
+        }
+        write!(f, "The node accepts the transaction")?;
+        if let (Some(vsize), Some(fee_in_sat)) = (self.vsize, self.fee_in_sat) {
+            write!(
+                f,
+                " | {} vB | fee: {} sats",
+                vsize.to_formatted_string(&Locale::en),
+                fee_in_sat.to_formatted_string(&Locale::en)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastStatus {
+    InMempool,
+    Confirmed {
+        confirmations: u64,
+    },
+    /// Not in the mempool, and neither indexed by the node nor among its confirmed unspents, e.g. evicted, or
+    /// confirmed and already spent on a node without `-txindex`.
+    NotFound,
+}
+
+impl Display for BroadcastStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BroadcastStatus::InMempool => write!(f, "waiting in the mempool"),
+            BroadcastStatus::Confirmed { confirmations } => write!(
+                f,
+                "confirmed ({} confirmations)",
+                confirmations.to_formatted_string(&Locale::en)
+            ),
+            BroadcastStatus::NotFound => {
+                write!(f, "not found in the mempool or the utxo set of the node")
+            }
+        }
+    }
+}
+
+/// A sweep sent to the network, tracked until it confirms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepBroadcast {
+    pub txid: Txid,
+    pub status: BroadcastStatus,
+}
+
+impl SweepBroadcast {
+    pub fn is_confirmed(&self) -> bool {
+        matches!(self.status, BroadcastStatus::Confirmed { .. })
+    }
+}
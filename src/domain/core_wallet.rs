@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+use bitcoin::Amount;
+use bitcoincore_rpc::jsonrpc::serde_json::Value;
+use num_format::{Locale, ToFormattedString};
+
+use super::descriptor_export::ImportDescriptorsVariant;
+
+/// Bitcoincore accepts most names, but the name also goes into the rpc url, so only url safe ones are allowed here.
+pub fn is_wallet_name_sane(wallet_name: &str) -> bool {
+    !wallet_name.is_empty()
+        && wallet_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+/// What the user asked bitcoincore to create.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreWalletRequest {
+    pub wallet_name: String,
+    pub watch_only: bool,
+}
+
+impl CoreWalletRequest {
+    pub fn variant(&self) -> ImportDescriptorsVariant {
+        if self.watch_only {
+            ImportDescriptorsVariant::WatchOnly
+        } else {
+            ImportDescriptorsVariant::Spendable
+        }
+    }
+
+    /// Positional arguments of `createwallet`: a blank descriptor wallet, without private keys when watch-only.
+    pub fn createwallet_args(&self) -> Vec<Value> {
+        vec![
+            Value::from(self.wallet_name.clone()),
+            Value::from(self.watch_only),
+            Value::from(true),
+            Value::from(""),
+            Value::from(false),
+            Value::from(true),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorImportResult {
+    pub label: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+impl DescriptorImportResult {
+    /// Reads one entry of the `importdescriptors` response.
+    pub fn from_response(label: String, response: &Value) -> Self {
+        DescriptorImportResult {
+            label,
+            success: response["success"].as_bool().unwrap_or(false),
+            error: response["error"]["message"]
+                .as_str()
+                .map(|message| message.to_string()),
+            warnings: response["warnings"]
+                .as_array()
+                .map(|warnings| {
+                    warnings
+                        .iter()
+                        .filter_map(|warning| warning.as_str().map(|warning| warning.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for DescriptorImportResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}",
+            self.label,
+            if self.success { "imported" } else { "failed" }
+        )?;
+        if let Some(error) = &self.error {
+            write!(f, " ({})", error)?;
+        }
+        for warning in self.warnings.iter() {
+            write!(f, " [warning: {}]", warning)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WalletBalance {
+    pub trusted_in_sat: u64,
+    pub untrusted_pending_in_sat: u64,
+    pub immature_in_sat: u64,
+}
+
+impl WalletBalance {
+    /// Reads the `mine` part of a `getbalances` response. Watch-only wallets report their coins there too.
+    pub fn from_getbalances(response: &Value) -> Option<Self> {
+        let to_sat = |value: &Value| {
+            value
+                .as_f64()
+                .and_then(|btc| Amount::from_btc(btc).ok())
+                .map(|amount| amount.to_sat())
+        };
+        let mine = &response["mine"];
+        Some(WalletBalance {
+            trusted_in_sat: to_sat(&mine["trusted"])?,
+            untrusted_pending_in_sat: to_sat(&mine["untrusted_pending"]).unwrap_or(0),
+            immature_in_sat: to_sat(&mine["immature"]).unwrap_or(0),
+        })
+    }
+}
+
+impl Display for WalletBalance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "trusted: {} sats | pending: {} sats | immature: {} sats",
+            self.trusted_in_sat.to_formatted_string(&Locale::en),
+            self.untrusted_pending_in_sat
+                .to_formatted_string(&Locale::en),
+            self.immature_in_sat.to_formatted_string(&Locale::en),
+        )
+    }
+}
+
+/// The outcome of sending the finds to a bitcoincore wallet, shown in the results area.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreWalletImportReport {
+    pub request: CoreWalletRequest,
+    /// `false` when a wallet with the same name already existed and was reused.
+    pub created: bool,
+    pub results: Vec<DescriptorImportResult>,
+    pub balance: Option<WalletBalance>,
+}
+
+impl CoreWalletImportReport {
+    pub fn imported_count(&self) -> usize {
+        self.results.iter().filter(|result| result.success).count()
+    }
+}
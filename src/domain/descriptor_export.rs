@@ -0,0 +1,145 @@
+use std::{fmt::Display, path::PathBuf};
+
+use bitceptron_retriever::{covered_descriptors::CoveredDescriptors, error::RetrieverError};
+use bitcoin::{bip32::Xpriv, key::Secp256k1};
+use bitcoincore_rpc::jsonrpc::serde_json;
+use miniscript::descriptor::checksum::desc_checksum;
+use serde::Serialize;
+
+use super::{
+    finds::PathDescriptorFind,
+    results_export::{export_file_path, write_export_file, write_private_export_file},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportDescriptorsVariant {
+    /// Public keys with their key origin. Core can watch the coins but not spend them.
+    WatchOnly,
+    /// The master xprv with the derivation path. Anyone holding the file can spend the coins.
+    Spendable,
+}
+
+impl ImportDescriptorsVariant {
+    fn file_prefix(&self) -> &'static str {
+        match self {
+            ImportDescriptorsVariant::WatchOnly => "importdescriptors_watch_only",
+            ImportDescriptorsVariant::Spendable => "importdescriptors_spendable",
+        }
+    }
+}
+
+impl Display for ImportDescriptorsVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportDescriptorsVariant::WatchOnly => write!(f, "watch-only"),
+            ImportDescriptorsVariant::Spendable => write!(f, "spendable"),
+        }
+    }
+}
+
+/// Where spendable descriptors go once the warning about the master xprv is confirmed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpendableDescriptorsDestination {
+    ExportFile,
+    CoreWallet,
+}
+
+/// One request of a bitcoincore `importdescriptors` call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportDescriptorRequest {
+    pub desc: String,
+    /// Unix time to rescan from. `0` rescans the whole chain.
+    pub timestamp: i64,
+    pub active: bool,
+    pub internal: bool,
+    pub label: String,
+}
+
+/// A find together with the time of the earliest block holding one of its unspents, if known.
+#[derive(Debug, Clone)]
+pub struct FindToImport {
+    pub find: PathDescriptorFind,
+    pub earliest_block_time: Option<i64>,
+}
+
+fn descriptor_template(script_type: &CoveredDescriptors) -> (&'static str, &'static str) {
+    match script_type {
+        CoveredDescriptors::P2pk => ("pk(", ")"),
+        CoveredDescriptors::P2pkh => ("pkh(", ")"),
+        CoveredDescriptors::P2wpkh => ("wpkh(", ")"),
+        CoveredDescriptors::P2shwpkh => ("sh(wpkh(", "))"),
+        CoveredDescriptors::P2tr => ("tr(", ")"),
+    }
+}
+
+/// The descriptor of a find with its checksum, keyed either by origin and public key or by the master xprv.
+pub fn import_descriptor_string(
+    find: &PathDescriptorFind,
+    master_xpriv: &Xpriv,
+    variant: ImportDescriptorsVariant,
+) -> Result<String, RetrieverError> {
+    let secp = Secp256k1::new();
+    let script_type = find.script_type().ok_or(RetrieverError::ConsensusEncodeError(
+        bitcoin::consensus::encode::Error::ParseFailed("descriptor type is not covered"),
+    ))?;
+    let path_steps = find
+        .path
+        .into_iter()
+        .map(|child_number| child_number.to_string())
+        .collect::<Vec<_>>();
+    let key = match variant {
+        ImportDescriptorsVariant::WatchOnly => {
+            let public_key = master_xpriv
+                .derive_priv(&secp, &find.path)?
+                .to_keypair(&secp)
+                .public_key();
+            let mut origin = vec![master_xpriv.fingerprint(&secp).to_string()];
+            origin.extend(path_steps);
+            format!("[{}]{}", origin.join("/"), public_key)
+        }
+        ImportDescriptorsVariant::Spendable => {
+            let mut key = vec![master_xpriv.to_string()];
+            key.extend(path_steps);
+            key.join("/")
+        }
+    };
+    let (prefix, suffix) = descriptor_template(&script_type);
+    let descriptor = format!("{}{}{}", prefix, key, suffix);
+    let checksum = desc_checksum(&descriptor)?;
+    Ok(format!("{}#{}", descriptor, checksum))
+}
+
+pub fn build_import_descriptor_requests(
+    finds_to_import: &[FindToImport],
+    master_xpriv: &Xpriv,
+    variant: ImportDescriptorsVariant,
+) -> Result<Vec<ImportDescriptorRequest>, RetrieverError> {
+    finds_to_import
+        .iter()
+        .map(|find_to_import| {
+            Ok(ImportDescriptorRequest {
+                desc: import_descriptor_string(&find_to_import.find, master_xpriv, variant)?,
+                timestamp: find_to_import.earliest_block_time.unwrap_or(0),
+                active: false,
+                internal: false,
+                label: format!("retriever {}", find_to_import.find.path),
+            })
+        })
+        .collect()
+}
+
+/// Writes the `importdescriptors` json to `{data_dir}/exports/`. Spendable exports are only readable by the owner.
+pub fn write_import_descriptors(
+    requests: &[ImportDescriptorRequest],
+    data_dir: &str,
+    variant: ImportDescriptorsVariant,
+) -> Result<PathBuf, RetrieverError> {
+    let content = serde_json::to_string_pretty(requests)
+        .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?;
+    let export_path = export_file_path(data_dir, variant.file_prefix(), "json");
+    match variant {
+        ImportDescriptorsVariant::WatchOnly => write_export_file(&export_path, &content)?,
+        ImportDescriptorsVariant::Spendable => write_private_export_file(&export_path, &content)?,
+    }
+    Ok(export_path)
+}
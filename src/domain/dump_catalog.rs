@@ -0,0 +1,160 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use num_format::{Locale, ToFormattedString};
+
+use super::dump_metadata::{format_unix_time, DumpMetadata};
+
+pub const LEGACY_DUMP_FILE_NAME: &str = "utxo_dump.dat";
+
+/// Dumps live in `{data_dir}/{chain}/utxo_dump_{height}.dat`.
+pub fn dump_file_name_for_height(height: u64) -> String {
+    format!("utxo_dump_{}.dat", height)
+}
+
+pub fn network_dir_in_data_dir(data_dir: &str, chain: &str) -> PathBuf {
+    let mut network_dir = PathBuf::from(data_dir);
+    network_dir.push(chain);
+    network_dir
+}
+
+/// A dump file found in the data dir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpFileEntry {
+    pub path: PathBuf,
+    /// Name of the network directory the dump sits in. `None` for the legacy dump in the data dir root.
+    pub network_dir: Option<String>,
+    pub size_in_bytes: u64,
+    pub modified: Option<i64>,
+    pub metadata: Option<DumpMetadata>,
+}
+
+impl DumpFileEntry {
+    pub fn from_path(path: &Path, network_dir: Option<String>) -> Option<Self> {
+        let file_metadata = fs::metadata(path).ok()?;
+        if !file_metadata.is_file() {
+            return None;
+        }
+        let modified = file_metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64);
+        Some(DumpFileEntry {
+            path: path.to_path_buf(),
+            network_dir,
+            size_in_bytes: file_metadata.len(),
+            modified,
+            metadata: DumpMetadata::read_sidecar(path),
+        })
+    }
+
+    pub fn chain(&self) -> Option<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.chain.clone())
+            .or(self.network_dir.clone())
+    }
+
+    pub fn height(&self) -> Option<u64> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.base_height)
+    }
+
+    pub fn date(&self) -> Option<i64> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.base_block_time)
+            .or(self.modified)
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn path_string(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{:<10} {:>12} {:>14} MB {:>22}  {}",
+            self.chain().unwrap_or("unknown".to_string()),
+            match self.height() {
+                Some(height) => height.to_formatted_string(&Locale::en),
+                None => "unknown".to_string(),
+            },
+            (self.size_in_bytes / 1_000_000).to_formatted_string(&Locale::en),
+            match self.date() {
+                Some(date) => format_unix_time(date),
+                None => "unknown".to_string(),
+            },
+            self.file_name(),
+        )
+    }
+}
+
+fn is_dump_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "dat")
+        && path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("utxo_dump"))
+}
+
+/// Lists the legacy dump in the data dir root and every dump in its network directories.
+/// Entries are sorted by chain and then by height, newest first.
+pub fn list_dump_files(data_dir: &str) -> Vec<DumpFileEntry> {
+    let mut entries = vec![];
+    let Ok(read_dir) = fs::read_dir(data_dir) else {
+        return entries;
+    };
+    for dir_entry in read_dir.flatten() {
+        let path = dir_entry.path();
+        if path.is_dir() {
+            let network_dir = dir_entry.file_name().to_string_lossy().to_string();
+            let Ok(network_read_dir) = fs::read_dir(&path) else {
+                continue;
+            };
+            for network_dir_entry in network_read_dir.flatten() {
+                let dump_path = network_dir_entry.path();
+                if is_dump_file(&dump_path) {
+                    entries.extend(DumpFileEntry::from_path(
+                        &dump_path,
+                        Some(network_dir.clone()),
+                    ));
+                }
+            }
+        } else if is_dump_file(&path) {
+            entries.extend(DumpFileEntry::from_path(&path, None));
+        }
+    }
+    entries.sort_by(|a, b| {
+        a.chain()
+            .cmp(&b.chain())
+            .then(b.height().cmp(&a.height()))
+            .then(b.modified.cmp(&a.modified))
+    });
+    entries
+}
+
+/// The newest dump taken from the given chain, if any.
+pub fn latest_dump_for_chain(data_dir: &str, chain: &str) -> Option<DumpFileEntry> {
+    list_dump_files(data_dir)
+        .into_iter()
+        .find(|entry| entry.chain().as_deref() == Some(chain))
+}
+
+pub fn delete_dump_file(path: &Path) -> std::io::Result<()> {
+    fs::remove_file(path)?;
+    let sidecar_path = DumpMetadata::sidecar_path(path);
+    if sidecar_path.exists() {
+        fs::remove_file(sidecar_path)?;
+    }
+    Ok(())
+}
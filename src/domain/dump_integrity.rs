@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use bitcoin::{
+    consensus::Encodable,
+    hashes::{sha256, sha256d, Hash},
+};
+use num_format::{Locale, ToFormattedString};
+
+/// First bitcoincore version whose `txoutset_hash` is `hash_serialized_3`, the per coin serialization hashed here.
+pub const MIN_NODE_VERSION_FOR_TXOUTSET_HASH: u64 = 260000;
+
+/// Recomputes bitcoincore's `hash_serialized_3` over the coins of a dump, in dump order.
+pub struct TxoutsetHasher {
+    engine: sha256::HashEngine,
+}
+
+impl Default for TxoutsetHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxoutsetHasher {
+    pub fn new() -> Self {
+        TxoutsetHasher {
+            engine: sha256d::Hash::engine(),
+        }
+    }
+
+    pub fn add(&mut self, txout: &txoutset::TxOut) {
+        let code = (txout.height << 1) + u32::from(txout.is_coinbase);
+        let amount = u64::from(txout.amount) as i64;
+        let _ = txout.out_point.consensus_encode(&mut self.engine);
+        let _ = code.consensus_encode(&mut self.engine);
+        let _ = amount.consensus_encode(&mut self.engine);
+        let _ = txout.script_pubkey.consensus_encode(&mut self.engine);
+    }
+
+    pub fn finalize(self) -> String {
+        sha256d::Hash::from_engine(self.engine).to_string()
+    }
+}
+
+/// What was read from a dump while populating, to be checked against what the node reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DumpReadSummary {
+    pub coins_in_header: u64,
+    pub coins_read: u64,
+    pub txoutset_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DumpIntegrityProblem {
+    CoinCountMismatch {
+        expected: u64,
+        read: u64,
+    },
+    TxoutsetHashMismatch {
+        expected: String,
+        computed: String,
+    },
+}
+
+impl Display for DumpIntegrityProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DumpIntegrityProblem::CoinCountMismatch { expected, read } => write!(
+                f,
+                "The dump should hold {} coins but {} could be read. The file is truncated or corrupted.",
+                expected.to_formatted_string(&Locale::en),
+                read.to_formatted_string(&Locale::en)
+            ),
+            DumpIntegrityProblem::TxoutsetHashMismatch { expected, computed } => write!(
+                f,
+                "The txoutset hash of the dump is {} but bitcoincore reported {} when it was created. The file is corrupted.",
+                computed, expected
+            ),
+        }
+    }
+}
+
+/// Compares a dump read summary against the coin count in the header and the hash recorded at creation.
+pub fn check_dump_integrity(
+    summary: &DumpReadSummary,
+    expected_txoutset_hash: Option<&str>,
+) -> Vec<DumpIntegrityProblem> {
+    let mut problems = vec![];
+    if summary.coins_read != summary.coins_in_header {
+        problems.push(DumpIntegrityProblem::CoinCountMismatch {
+            expected: summary.coins_in_header,
+            read: summary.coins_read,
+        });
+    }
+    if let (Some(expected), Some(computed)) = (expected_txoutset_hash, &summary.txoutset_hash) {
+        if expected != computed {
+            problems.push(DumpIntegrityProblem::TxoutsetHashMismatch {
+                expected: expected.to_string(),
+                computed: computed.to_string(),
+            });
+        }
+    }
+    problems
+}
@@ -0,0 +1,123 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bitceptron_retriever::{
+    client::dump_utxout_set_result::DumpTxoutSetResult, error::RetrieverError,
+};
+use bitcoincore_rpc::jsonrpc::serde_json;
+use chrono::{DateTime, Utc};
+use num_format::{Locale, ToFormattedString};
+use serde::{Deserialize, Serialize};
+
+use super::dump_integrity::MIN_NODE_VERSION_FOR_TXOUTSET_HASH;
+
+/// Default number of blocks a dump may lag behind the node's tip before it is flagged as stale.
+pub const DEFAULT_STALE_DUMP_THRESHOLD_IN_BLOCKS: u64 = 144;
+
+/// What is known about a utxo dump file. Kept in a sidecar json file next to the dump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DumpMetadata {
+    pub base_height: Option<u64>,
+    pub base_hash: String,
+    pub coins_written: u64,
+    /// Only known when the dump was created by this app, as it comes from the `dumptxoutset` response.
+    pub txoutset_hash: Option<String>,
+    pub nchaintx: Option<u64>,
+    pub chain: Option<String>,
+    /// Unix time of the block the dump was taken at.
+    pub base_block_time: Option<i64>,
+    /// Unix time the dump (or, for older dumps, this sidecar) was written.
+    pub created_at: i64,
+    /// Version of the bitcoincore that wrote the dump, as reported by `getnetworkinfo`.
+    #[serde(default)]
+    pub node_version: Option<u64>,
+}
+
+impl DumpMetadata {
+    pub fn from_dump_result(
+        dump_result: &DumpTxoutSetResult,
+        chain: Option<String>,
+        base_block_time: Option<i64>,
+        node_version: Option<u64>,
+    ) -> Self {
+        DumpMetadata {
+            base_height: Some(*dump_result.get_base_height()),
+            base_hash: dump_result.get_base_hash().to_owned(),
+            coins_written: *dump_result.get_coins_written(),
+            txoutset_hash: Some(dump_result.get_txoutset_hash().to_owned()),
+            nchaintx: Some(*dump_result.get_nchaintx()),
+            chain,
+            base_block_time,
+            created_at: Utc::now().timestamp(),
+            node_version,
+        }
+    }
+
+    pub fn sidecar_path(dump_file_path: &Path) -> PathBuf {
+        let mut file_name = dump_file_path.file_name().unwrap_or_default().to_owned();
+        file_name.push(".meta.json");
+        dump_file_path.with_file_name(file_name)
+    }
+
+    pub fn write_sidecar(&self, dump_file_path: &Path) -> Result<(), RetrieverError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?;
+        fs::write(Self::sidecar_path(dump_file_path), content)?;
+        Ok(())
+    }
+
+    pub fn read_sidecar(dump_file_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(Self::sidecar_path(dump_file_path)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn blocks_behind(&self, tip_height: u64) -> Option<u64> {
+        self.base_height
+            .map(|base_height| tip_height.saturating_sub(base_height))
+    }
+
+    pub fn is_stale(&self, tip_height: u64, threshold_in_blocks: u64) -> bool {
+        self.blocks_behind(tip_height)
+            .is_some_and(|behind| behind > threshold_in_blocks)
+    }
+
+    /// The recorded txoutset hash, if the node that wrote it hashes coins the way the integrity check does.
+    pub fn comparable_txoutset_hash(&self) -> Option<&str> {
+        match self.node_version {
+            Some(version) if version >= MIN_NODE_VERSION_FOR_TXOUTSET_HASH => {
+                self.txoutset_hash.as_deref()
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn format_unix_time(unix_time: i64) -> String {
+    match DateTime::from_timestamp(unix_time, 0) {
+        Some(date_time) => date_time.format("%Y-%m-%d %H:%M UTC").to_string(),
+        None => "unknown".to_string(),
+    }
+}
+
+impl std::fmt::Display for DumpMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "height: {} | block: {} | block time: {} | coins: {} | chain: {} | created: {}",
+            match self.base_height {
+                Some(height) => height.to_formatted_string(&Locale::en),
+                None => "unknown".to_string(),
+            },
+            self.base_hash,
+            match self.base_block_time {
+                Some(time) => format_unix_time(time),
+                None => "unknown".to_string(),
+            },
+            self.coins_written.to_formatted_string(&Locale::en),
+            self.chain.as_deref().unwrap_or("unknown"),
+            format_unix_time(self.created_at),
+        )
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt::Display;
+
+use bitceptron_retriever::client::BitcoincoreRpcClient;
+use num_format::{Locale, ToFormattedString};
+
+/// Rough upper estimate of the bytes a single coin takes in a `dumptxoutset` file.
+pub const ESTIMATED_DUMP_BYTES_PER_COIN: u64 = 70;
+/// Extra room kept on top of the estimated dump size before the disk is considered too small.
+pub const DUMP_SIZE_SAFETY_MARGIN_PERCENT: u64 = 10;
+
+/// State of the node and the data dir collected before asking bitcoincore for a new dump.
+#[derive(Debug, Clone)]
+pub struct DumpPreflightReport {
+    pub chain: String,
+    pub blocks: u64,
+    pub headers: u64,
+    pub initial_block_download: bool,
+    pub verification_progress: f64,
+    pub pruned: bool,
+    pub prune_height: Option<u64>,
+    pub utxo_count: Option<u64>,
+    pub free_space_in_bytes: Option<u64>,
+}
+
+impl DumpPreflightReport {
+    pub fn expected_dump_size_in_bytes(&self) -> Option<u64> {
+        self.utxo_count
+            .map(|count| count * ESTIMATED_DUMP_BYTES_PER_COIN)
+    }
+
+    pub fn problems(&self) -> Vec<DumpPreflightProblem> {
+        let mut problems = vec![];
+        if self.initial_block_download {
+            problems.push(DumpPreflightProblem::InitialBlockDownload {
+                verification_progress: self.verification_progress,
+            });
+        }
+        if self.blocks < self.headers {
+            problems.push(DumpPreflightProblem::BlocksBehindHeaders {
+                blocks: self.blocks,
+                headers: self.headers,
+            });
+        }
+        if self.pruned {
+            problems.push(DumpPreflightProblem::Pruned {
+                prune_height: self.prune_height,
+            });
+        }
+        if let (Some(free), Some(expected)) =
+            (self.free_space_in_bytes, self.expected_dump_size_in_bytes())
+        {
+            let required = expected + expected * DUMP_SIZE_SAFETY_MARGIN_PERCENT / 100;
+            if free < required {
+                problems.push(DumpPreflightProblem::InsufficientDiskSpace {
+                    free_in_bytes: free,
+                    required_in_bytes: required,
+                });
+            }
+        }
+        problems
+    }
+
+    pub fn has_problems(&self) -> bool {
+        !self.problems().is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DumpPreflightProblem {
+    InitialBlockDownload {
+        verification_progress: f64,
+    },
+    BlocksBehindHeaders {
+        blocks: u64,
+        headers: u64,
+    },
+    Pruned {
+        prune_height: Option<u64>,
+    },
+    InsufficientDiskSpace {
+        free_in_bytes: u64,
+        required_in_bytes: u64,
+    },
+}
+
+impl Display for DumpPreflightProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DumpPreflightProblem::InitialBlockDownload {
+                verification_progress,
+            } => write!(
+                f,
+                "The node is still in initial block download ({:.2}% verified). A dump taken now reflects an old chain state and will miss recent coins.",
+                verification_progress * 100.
+            ),
+            DumpPreflightProblem::BlocksBehindHeaders { blocks, headers } => write!(
+                f,
+                "The node has validated {} blocks out of {} known headers ({} behind). Coins created in the missing blocks will not be in the dump.",
+                blocks.to_formatted_string(&Locale::en),
+                headers.to_formatted_string(&Locale::en),
+                (headers - blocks).to_formatted_string(&Locale::en)
+            ),
+            DumpPreflightProblem::Pruned { prune_height } => write!(
+                f,
+                "The node is pruned{}. The dump itself is complete, but looking up the history of found coins below the prune height will not be possible on this node.",
+                match prune_height {
+                    Some(height) => format!(" (blocks below {} are gone)", height.to_formatted_string(&Locale::en)),
+                    None => "".to_string(),
+                }
+            ),
+            DumpPreflightProblem::InsufficientDiskSpace {
+                free_in_bytes,
+                required_in_bytes,
+            } => write!(
+                f,
+                "The data dir has {} MB free but the dump is expected to need about {} MB. Bitcoincore may fail half way and leave a truncated file behind.",
+                (free_in_bytes / 1_000_000).to_formatted_string(&Locale::en),
+                (required_in_bytes / 1_000_000).to_formatted_string(&Locale::en)
+            ),
+        }
+    }
+}
+
+/// Which dump action is waiting for the preflight confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpIntent {
+    CreateNew,
+    UseOrCreate,
+}
+
+#[cfg(unix)]
+pub fn free_space_in_bytes(dir: &str) -> Option<u64> {
+    let path = std::ffi::CString::new(dir).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::statvfs(path.as_ptr(), &mut stat) };
+    if result != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn free_space_in_bytes(_dir: &str) -> Option<u64> {
+    None
+}
+
+/// A dump waiting for the user to acknowledge the preflight problems.
+#[derive(Debug, Clone)]
+pub struct PendingDump {
+    pub intent: DumpIntent,
+    pub client: BitcoincoreRpcClient,
+    pub report: DumpPreflightReport,
+}
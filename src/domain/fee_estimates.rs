@@ -0,0 +1,76 @@
+use std::fmt::Display;
+
+use bitcoincore_rpc::jsonrpc::serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeePriority {
+    Economy,
+    Normal,
+    Priority,
+}
+
+impl FeePriority {
+    pub const ALL: [FeePriority; 3] = [
+        FeePriority::Economy,
+        FeePriority::Normal,
+        FeePriority::Priority,
+    ];
+
+    /// The `estimatesmartfee` confirmation target, in blocks.
+    pub fn confirmation_target(&self) -> u16 {
+        match self {
+            FeePriority::Economy => 144,
+            FeePriority::Normal => 6,
+            FeePriority::Priority => 2,
+        }
+    }
+}
+
+impl Display for FeePriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeePriority::Economy => write!(f, "economy"),
+            FeePriority::Normal => write!(f, "normal"),
+            FeePriority::Priority => write!(f, "priority"),
+        }
+    }
+}
+
+/// Bitcoincore reports fee rates in btc per kvB.
+fn btc_per_kvb_to_sat_per_vb(btc_per_kvb: f64) -> f64 {
+    btc_per_kvb * 100_000_000. / 1_000.
+}
+
+/// Fee rates suggested by the node, in sats per vbyte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeEstimates {
+    /// One entry per `FeePriority::ALL`, `None` when the node has too little data to estimate.
+    pub estimates: Vec<(FeePriority, Option<f64>)>,
+    /// The higher of the node's minimum relay fee and its current mempool minimum fee.
+    pub min_fee_rate_sat_per_vb: f64,
+}
+
+impl FeeEstimates {
+    /// Reads the `feerate` of an `estimatesmartfee` response.
+    pub fn fee_rate_from_estimatesmartfee(response: &Value) -> Option<f64> {
+        response["feerate"].as_f64().map(btc_per_kvb_to_sat_per_vb)
+    }
+
+    /// Reads the fee floor of a `getmempoolinfo` response.
+    pub fn min_fee_rate_from_getmempoolinfo(response: &Value) -> Option<f64> {
+        let min_relay_fee = response["minrelaytxfee"].as_f64()?;
+        let mempool_min_fee = response["mempoolminfee"].as_f64().unwrap_or(min_relay_fee);
+        Some(btc_per_kvb_to_sat_per_vb(
+            min_relay_fee.max(mempool_min_fee),
+        ))
+    }
+
+    /// The estimate for a priority, never below what the node would relay.
+    pub fn fee_rate_for(&self, priority: FeePriority) -> Option<f64> {
+        self.estimates
+            .iter()
+            .find(|(estimate_priority, _)| *estimate_priority == priority)
+            .and_then(|(_, fee_rate)| *fee_rate)
+            .map(|fee_rate| fee_rate.max(self.min_fee_rate_sat_per_vb))
+    }
+}
@@ -0,0 +1,110 @@
+use bitceptron_retriever::{
+    covered_descriptors::CoveredDescriptors,
+    path_pairs::{PathDescriptorPair, PathScanResultDescriptorTrio},
+};
+use bitcoin::{bip32::DerivationPath, secp256k1::PublicKey, OutPoint, ScriptBuf};
+use miniscript::{descriptor::DescriptorType, Descriptor};
+
+pub fn script_type_of(descriptor: &Descriptor<PublicKey>) -> Option<CoveredDescriptors> {
+    match descriptor.desc_type() {
+        DescriptorType::Bare => Some(CoveredDescriptors::P2pk),
+        DescriptorType::Pkh => Some(CoveredDescriptors::P2pkh),
+        DescriptorType::Wpkh => Some(CoveredDescriptors::P2wpkh),
+        DescriptorType::ShWpkh => Some(CoveredDescriptors::P2shwpkh),
+        DescriptorType::Tr => Some(CoveredDescriptors::P2tr),
+        _ => None,
+    }
+}
+
+/// A derivation path whose script pubkey was found in the utxo set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathDescriptorFind {
+    pub path: DerivationPath,
+    pub descriptor: Descriptor<PublicKey>,
+}
+
+impl PathDescriptorFind {
+    pub fn new(path: DerivationPath, descriptor: Descriptor<PublicKey>) -> Self {
+        PathDescriptorFind { path, descriptor }
+    }
+
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        self.descriptor.script_pubkey()
+    }
+
+    pub fn script_type(&self) -> Option<CoveredDescriptors> {
+        script_type_of(&self.descriptor)
+    }
+
+    pub fn to_path_descriptor_pair(&self) -> PathDescriptorPair {
+        PathDescriptorPair::new(self.path.clone(), self.descriptor.clone())
+    }
+}
+
+/// A single unspent output paying to a find.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundUtxo {
+    pub out_point: OutPoint,
+    pub amount_in_sat: u64,
+    /// Height of the block the output was created in.
+    pub height: u64,
+    /// Only known when the details come from the dump, as `scantxoutset` does not report it.
+    pub is_coinbase: Option<bool>,
+}
+
+impl FoundUtxo {
+    /// Confirmations of the output as seen at the given height.
+    pub fn confirmations(&self, at_height: u64) -> Option<u64> {
+        at_height
+            .checked_sub(self.height)
+            .map(|blocks_after| blocks_after + 1)
+    }
+}
+
+/// A find together with its unspent outputs, fetched from bitcoincore or read from the dump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetailedFind {
+    pub path: DerivationPath,
+    pub descriptor: Descriptor<PublicKey>,
+    pub unspents: Vec<FoundUtxo>,
+    /// Height of the utxo set the unspents were read from.
+    pub at_height: Option<u64>,
+}
+
+impl DetailedFind {
+    pub fn from_find(
+        find: &PathDescriptorFind,
+        unspents: Vec<FoundUtxo>,
+        at_height: Option<u64>,
+    ) -> Self {
+        DetailedFind {
+            path: find.path.clone(),
+            descriptor: find.descriptor.clone(),
+            unspents,
+            at_height,
+        }
+    }
+
+    pub fn from_scan_result(scan_result: &PathScanResultDescriptorTrio) -> Self {
+        DetailedFind {
+            path: scan_result.0.clone(),
+            descriptor: scan_result.2.clone(),
+            unspents: scan_result
+                .1
+                .unspents
+                .iter()
+                .map(|utxo| FoundUtxo {
+                    out_point: OutPoint::new(utxo.txid, utxo.vout),
+                    amount_in_sat: utxo.amount.to_sat(),
+                    height: utxo.height,
+                    is_coinbase: None,
+                })
+                .collect(),
+            at_height: scan_result.1.height,
+        }
+    }
+
+    pub fn total_amount_in_sat(&self) -> u64 {
+        self.unspents.iter().map(|utxo| utxo.amount_in_sat).sum()
+    }
+}
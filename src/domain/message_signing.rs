@@ -0,0 +1,536 @@
+use std::fmt::Display;
+
+use bitcoin::{
+    absolute::LockTime,
+    base64::{prelude::BASE64_STANDARD, Engine},
+    bip32::{DerivationPath, Xpriv},
+    consensus::{deserialize, serialize},
+    ecdsa,
+    hashes::{sha256, Hash, HashEngine},
+    key::{Keypair, Secp256k1, TapTweak, XOnlyPublicKey},
+    opcodes::{all::OP_RETURN, OP_0},
+    script::{Builder, Instruction, PushBytesBuf},
+    secp256k1::{All, Message},
+    sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType},
+    sign_message::{signed_msg_hash, MessageSignature},
+    taproot,
+    transaction::Version,
+    Address, AddressType, Amount, OutPoint, PublicKey, ScriptBuf, Sequence, Transaction, TxIn,
+    TxOut, Txid, WPubkeyHash, Witness,
+};
+
+const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageSignatureFormat {
+    /// The `signmessage` format of bitcoincore, only defined for p2pkh addresses.
+    Legacy,
+    /// Bip322 with only the witness of the signing transaction.
+    Bip322Simple,
+    /// Bip322 with the whole signing transaction, needed when the script_sig is not empty.
+    Bip322Full,
+}
+
+impl Display for MessageSignatureFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageSignatureFormat::Legacy => write!(f, "legacy signmessage"),
+            MessageSignatureFormat::Bip322Simple => write!(f, "bip322 simple"),
+            MessageSignatureFormat::Bip322Full => write!(f, "bip322 full"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageSigningProblem {
+    InvalidAddress(String),
+    UnsupportedAddress,
+    KeyDerivation(String),
+    KeyDoesNotMatchAddress,
+    InvalidSignatureEncoding,
+    InvalidSignature,
+}
+
+impl Display for MessageSigningProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageSigningProblem::InvalidAddress(reason) => {
+                write!(f, "The address is not valid: {}", reason)
+            }
+            MessageSigningProblem::UnsupportedAddress => write!(
+                f,
+                "Only p2pkh, p2wpkh, p2sh-p2wpkh and p2tr addresses can sign messages here."
+            ),
+            MessageSigningProblem::KeyDerivation(reason) => {
+                write!(f, "The key could not be derived: {}", reason)
+            }
+            MessageSigningProblem::KeyDoesNotMatchAddress => {
+                write!(f, "The key of the find does not belong to this address.")
+            }
+            MessageSigningProblem::InvalidSignatureEncoding => write!(
+                f,
+                "The signature is not a base64 encoded legacy or bip322 signature."
+            ),
+            MessageSigningProblem::InvalidSignature => write!(
+                f,
+                "The signature is not valid for this address and message."
+            ),
+        }
+    }
+}
+
+/// A message signed with the key of a find.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedMessage {
+    pub address: Address,
+    pub message: String,
+    pub format: MessageSignatureFormat,
+    /// Base64, as wallets and exchanges expect it.
+    pub signature: String,
+}
+
+/// The bip340 tagged hash of the message.
+fn bip322_message_hash(message: &str) -> [u8; 32] {
+    let tag_hash = sha256::Hash::hash(BIP322_TAG);
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag_hash.as_byte_array());
+    engine.input(tag_hash.as_byte_array());
+    engine.input(message.as_bytes());
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+/// The virtual transaction that pays the message to the address.
+fn bip322_to_spend(script_pubkey: &ScriptBuf, message: &str) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: Txid::all_zeros(),
+                vout: 0xffffffff,
+            },
+            script_sig: Builder::new()
+                .push_opcode(OP_0)
+                .push_slice(bip322_message_hash(message))
+                .into_script(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: script_pubkey.clone(),
+        }],
+    }
+}
+
+/// The virtual transaction whose signature proves control of the address.
+fn bip322_to_sign(to_spend: &Transaction) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: to_spend.txid(),
+                vout: 0,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Builder::new().push_opcode(OP_RETURN).into_script(),
+        }],
+    }
+}
+
+fn p2wpkh_script(public_key: &PublicKey) -> ScriptBuf {
+    ScriptBuf::new_p2wpkh(&WPubkeyHash::hash(&public_key.to_bytes()))
+}
+
+/// Signs a bip322 `to_sign` spending a p2wpkh output, or a p2sh-p2wpkh one given its redeem script.
+fn sign_p2wpkh_input(
+    secp: &Secp256k1<All>,
+    keypair: &Keypair,
+    to_sign: &Transaction,
+    witness_script: &ScriptBuf,
+) -> Result<Witness, MessageSigningProblem> {
+    let sighash = SighashCache::new(to_sign)
+        .p2wpkh_signature_hash(0, witness_script, Amount::ZERO, EcdsaSighashType::All)
+        .map_err(|_| MessageSigningProblem::KeyDoesNotMatchAddress)?;
+    let signature = ecdsa::Signature {
+        sig: secp.sign_ecdsa_low_r(
+            &Message::from_digest(sighash.to_byte_array()),
+            &keypair.secret_key(),
+        ),
+        hash_ty: EcdsaSighashType::All,
+    };
+    Ok(Witness::p2wpkh(&signature, &keypair.public_key()))
+}
+
+/// Signs the message with the key at `path`, in bip322 for segwit and taproot addresses and in the legacy format for p2pkh.
+pub fn sign_message(
+    master_xpriv: &Xpriv,
+    path: &DerivationPath,
+    address: &Address,
+    message: &str,
+) -> Result<SignedMessage, MessageSigningProblem> {
+    let secp = Secp256k1::new();
+    let keypair = master_xpriv
+        .derive_priv(&secp, path)
+        .map_err(|e| MessageSigningProblem::KeyDerivation(e.to_string()))?
+        .to_keypair(&secp);
+    let public_key = PublicKey::new(keypair.public_key());
+    let script_pubkey = address.script_pubkey();
+    let to_spend = bip322_to_spend(&script_pubkey, message);
+    let mut to_sign = bip322_to_sign(&to_spend);
+    let (format, signature) = match address.address_type() {
+        Some(AddressType::P2pkh) => {
+            if script_pubkey != ScriptBuf::new_p2pkh(&public_key.pubkey_hash()) {
+                return Err(MessageSigningProblem::KeyDoesNotMatchAddress);
+            }
+            let signature = secp.sign_ecdsa_recoverable(
+                &Message::from_digest(signed_msg_hash(message).to_byte_array()),
+                &keypair.secret_key(),
+            );
+            (
+                MessageSignatureFormat::Legacy,
+                MessageSignature::new(signature, true).to_base64(),
+            )
+        }
+        Some(AddressType::P2wpkh) => {
+            if script_pubkey != p2wpkh_script(&public_key) {
+                return Err(MessageSigningProblem::KeyDoesNotMatchAddress);
+            }
+            let witness = sign_p2wpkh_input(&secp, &keypair, &to_sign, &script_pubkey)?;
+            (
+                MessageSignatureFormat::Bip322Simple,
+                BASE64_STANDARD.encode(serialize(&witness)),
+            )
+        }
+        Some(AddressType::P2sh) => {
+            let redeem_script = p2wpkh_script(&public_key);
+            if script_pubkey != redeem_script.to_p2sh() {
+                return Err(MessageSigningProblem::KeyDoesNotMatchAddress);
+            }
+            let witness = sign_p2wpkh_input(&secp, &keypair, &to_sign, &redeem_script)?;
+            let redeem_script_push = PushBytesBuf::try_from(redeem_script.to_bytes())
+                .map_err(|_| MessageSigningProblem::KeyDoesNotMatchAddress)?;
+            to_sign.input[0].script_sig =
+                Builder::new().push_slice(redeem_script_push).into_script();
+            to_sign.input[0].witness = witness;
+            (
+                MessageSignatureFormat::Bip322Full,
+                BASE64_STANDARD.encode(serialize(&to_sign)),
+            )
+        }
+        Some(AddressType::P2tr) => {
+            let (internal_key, _) = XOnlyPublicKey::from_keypair(&keypair);
+            if script_pubkey != ScriptBuf::new_p2tr(&secp, internal_key, None) {
+                return Err(MessageSigningProblem::KeyDoesNotMatchAddress);
+            }
+            let sighash = SighashCache::new(&to_sign)
+                .taproot_key_spend_signature_hash(
+                    0,
+                    &Prevouts::All(&to_spend.output),
+                    TapSighashType::Default,
+                )
+                .map_err(|_| MessageSigningProblem::KeyDoesNotMatchAddress)?;
+            let signature = taproot::Signature {
+                sig: secp.sign_schnorr(
+                    &Message::from_digest(sighash.to_byte_array()),
+                    &keypair.tap_tweak(&secp, None).to_inner(),
+                ),
+                hash_ty: TapSighashType::Default,
+            };
+            let mut witness = Witness::new();
+            witness.push(signature.to_vec());
+            (
+                MessageSignatureFormat::Bip322Simple,
+                BASE64_STANDARD.encode(serialize(&witness)),
+            )
+        }
+        _ => return Err(MessageSigningProblem::UnsupportedAddress),
+    };
+    Ok(SignedMessage {
+        address: address.clone(),
+        message: message.to_string(),
+        format,
+        signature,
+    })
+}
+
+/// Checks the witness of a p2wpkh spend, `script_code_source` being the output script or the p2sh-p2wpkh redeem script.
+fn verify_p2wpkh_witness(
+    secp: &Secp256k1<All>,
+    to_sign: &Transaction,
+    witness: &Witness,
+    script_code_source: &ScriptBuf,
+) -> Result<(), MessageSigningProblem> {
+    let (Some(signature), Some(public_key), 2) = (witness.nth(0), witness.nth(1), witness.len())
+    else {
+        return Err(MessageSigningProblem::InvalidSignature);
+    };
+    let signature = ecdsa::Signature::from_slice(signature)
+        .map_err(|_| MessageSigningProblem::InvalidSignature)?;
+    let public_key =
+        PublicKey::from_slice(public_key).map_err(|_| MessageSigningProblem::InvalidSignature)?;
+    if *script_code_source != p2wpkh_script(&public_key) {
+        return Err(MessageSigningProblem::InvalidSignature);
+    }
+    let sighash = SighashCache::new(to_sign)
+        .p2wpkh_signature_hash(0, script_code_source, Amount::ZERO, signature.hash_ty)
+        .map_err(|_| MessageSigningProblem::InvalidSignature)?;
+    secp.verify_ecdsa(
+        &Message::from_digest(sighash.to_byte_array()),
+        &signature.sig,
+        &public_key.inner,
+    )
+    .map_err(|_| MessageSigningProblem::InvalidSignature)
+}
+
+/// Verifies a pasted legacy or bip322 signature, returning the format it was in.
+pub fn verify_message(
+    address: &Address,
+    message: &str,
+    signature: &str,
+) -> Result<MessageSignatureFormat, MessageSigningProblem> {
+    let secp = Secp256k1::new();
+    let signature_bytes = BASE64_STANDARD
+        .decode(signature.trim())
+        .map_err(|_| MessageSigningProblem::InvalidSignatureEncoding)?;
+    let script_pubkey = address.script_pubkey();
+    if address.address_type() == Some(AddressType::P2pkh) {
+        let legacy_signature = MessageSignature::from_slice(&signature_bytes)
+            .map_err(|_| MessageSigningProblem::InvalidSignatureEncoding)?;
+        return match legacy_signature.is_signed_by_address(&secp, address, signed_msg_hash(message))
+        {
+            Ok(true) => Ok(MessageSignatureFormat::Legacy),
+            _ => Err(MessageSigningProblem::InvalidSignature),
+        };
+    }
+    let to_spend = bip322_to_spend(&script_pubkey, message);
+    let expected_to_sign = bip322_to_sign(&to_spend);
+    let (format, to_sign) = if let Ok(witness) = deserialize::<Witness>(&signature_bytes) {
+        let mut to_sign = expected_to_sign;
+        to_sign.input[0].witness = witness;
+        (MessageSignatureFormat::Bip322Simple, to_sign)
+    } else if let Ok(to_sign) = deserialize::<Transaction>(&signature_bytes) {
+        let mut unsigned_to_sign = to_sign.clone();
+        for input in unsigned_to_sign.input.iter_mut() {
+            input.script_sig = ScriptBuf::new();
+            input.witness = Witness::new();
+        }
+        if unsigned_to_sign != expected_to_sign {
+            return Err(MessageSigningProblem::InvalidSignature);
+        }
+        (MessageSignatureFormat::Bip322Full, to_sign)
+    } else {
+        return Err(MessageSigningProblem::InvalidSignatureEncoding);
+    };
+    let script_sig = &to_sign.input[0].script_sig;
+    let witness = &to_sign.input[0].witness;
+    match address.address_type() {
+        Some(AddressType::P2wpkh) if script_sig.is_empty() => {
+            verify_p2wpkh_witness(&secp, &to_sign, witness, &script_pubkey)?
+        }
+        Some(AddressType::P2sh) => {
+            let mut instructions = script_sig.instructions();
+            let (Some(Ok(Instruction::PushBytes(redeem_script))), None) =
+                (instructions.next(), instructions.next())
+            else {
+                return Err(MessageSigningProblem::InvalidSignature);
+            };
+            let redeem_script = ScriptBuf::from_bytes(redeem_script.as_bytes().to_vec());
+            if redeem_script.to_p2sh() != script_pubkey {
+                return Err(MessageSigningProblem::InvalidSignature);
+            }
+            verify_p2wpkh_witness(&secp, &to_sign, witness, &redeem_script)?
+        }
+        Some(AddressType::P2tr) if script_sig.is_empty() => {
+            let (Some(signature), 1) = (witness.nth(0), witness.len()) else {
+                return Err(MessageSigningProblem::InvalidSignature);
+            };
+            let signature = taproot::Signature::from_slice(signature)
+                .map_err(|_| MessageSigningProblem::InvalidSignature)?;
+            let output_key = XOnlyPublicKey::from_slice(&script_pubkey.as_bytes()[2..])
+                .map_err(|_| MessageSigningProblem::InvalidSignature)?;
+            let sighash = SighashCache::new(&to_sign)
+                .taproot_key_spend_signature_hash(
+                    0,
+                    &Prevouts::All(&to_spend.output),
+                    signature.hash_ty,
+                )
+                .map_err(|_| MessageSigningProblem::InvalidSignature)?;
+            secp.verify_schnorr(
+                &signature.sig,
+                &Message::from_digest(sighash.to_byte_array()),
+                &output_key,
+            )
+            .map_err(|_| MessageSigningProblem::InvalidSignature)?
+        }
+        Some(AddressType::P2wpkh) | Some(AddressType::P2tr) => {
+            return Err(MessageSigningProblem::InvalidSignature)
+        }
+        _ => return Err(MessageSigningProblem::UnsupportedAddress),
+    }
+    Ok(format)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::{
+        bip32::{ChainCode, ChildNumber, DerivationPath, Fingerprint, Xpriv},
+        key::Secp256k1,
+        Address, Network, PrivateKey, PublicKey,
+    };
+
+    use super::{
+        bip322_message_hash, bip322_to_sign, bip322_to_spend, sign_message, verify_message,
+        MessageSignatureFormat, MessageSigningProblem,
+    };
+
+    // The test vectors of bip322.
+    const BIP322_ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+    const BIP322_PRIVATE_KEY: &str = "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k";
+    const BIP322_EMPTY_MESSAGE_SIGNATURE: &str = "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+    const BIP322_HELLO_WORLD_SIGNATURE: &str = "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+
+    fn bip322_address() -> Address {
+        Address::from_str(BIP322_ADDRESS)
+            .unwrap()
+            .require_network(Network::Bitcoin)
+            .unwrap()
+    }
+
+    /// The vector key as a master key, so signing at the empty path uses it as is.
+    fn bip322_master_xpriv() -> Xpriv {
+        Xpriv {
+            network: Network::Bitcoin,
+            depth: 0,
+            parent_fingerprint: Fingerprint::default(),
+            child_number: ChildNumber::from_normal_idx(0).unwrap(),
+            private_key: PrivateKey::from_wif(BIP322_PRIVATE_KEY).unwrap().inner,
+            chain_code: ChainCode::from([0u8; 32]),
+        }
+    }
+
+    #[test]
+    fn message_hashes_match_bip322() {
+        assert_eq!(
+            hex::encode(bip322_message_hash("")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(bip322_message_hash("Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn virtual_transactions_match_bip322() {
+        let script_pubkey = bip322_address().script_pubkey();
+        for (message, to_spend_txid, to_sign_txid) in [
+            (
+                "",
+                "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7",
+                "1e9654e951a5ba44c8604c4de6c67fd78a27e81dcadcfe1edf638ba3aaebaed6",
+            ),
+            (
+                "Hello World",
+                "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b",
+                "88737ae86f2077145f93cc4b153ae9a1cb8d56afa511988c149c5c8c9d93bddf",
+            ),
+        ] {
+            let to_spend = bip322_to_spend(&script_pubkey, message);
+            assert_eq!(to_spend.txid().to_string(), to_spend_txid);
+            assert_eq!(bip322_to_sign(&to_spend).txid().to_string(), to_sign_txid);
+        }
+    }
+
+    #[test]
+    fn p2wpkh_signatures_match_bip322() {
+        let address = bip322_address();
+        for (message, signature) in [
+            ("", BIP322_EMPTY_MESSAGE_SIGNATURE),
+            ("Hello World", BIP322_HELLO_WORLD_SIGNATURE),
+        ] {
+            assert_eq!(
+                verify_message(&address, message, signature),
+                Ok(MessageSignatureFormat::Bip322Simple)
+            );
+            let signed_message = sign_message(
+                &bip322_master_xpriv(),
+                &DerivationPath::master(),
+                &address,
+                message,
+            )
+            .unwrap();
+            assert_eq!(signed_message.format, MessageSignatureFormat::Bip322Simple);
+            assert_eq!(signed_message.signature, signature);
+        }
+        assert_eq!(
+            verify_message(&address, "Hello World", BIP322_EMPTY_MESSAGE_SIGNATURE),
+            Err(MessageSigningProblem::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn signatures_of_every_address_type_verify() {
+        let secp = Secp256k1::new();
+        let master_xpriv = Xpriv::new_master(Network::Bitcoin, &[7u8; 32]).unwrap();
+        let path = DerivationPath::from_str("m/84'/0'/0'/0/3").unwrap();
+        let public_key = PublicKey::new(
+            master_xpriv
+                .derive_priv(&secp, &path)
+                .unwrap()
+                .to_keypair(&secp)
+                .public_key(),
+        );
+        let (internal_key, _) = public_key.inner.x_only_public_key();
+        for (address, format) in [
+            (
+                Address::p2pkh(&public_key, Network::Bitcoin),
+                MessageSignatureFormat::Legacy,
+            ),
+            (
+                Address::p2wpkh(&public_key, Network::Bitcoin).unwrap(),
+                MessageSignatureFormat::Bip322Simple,
+            ),
+            (
+                Address::p2shwpkh(&public_key, Network::Bitcoin).unwrap(),
+                MessageSignatureFormat::Bip322Full,
+            ),
+            (
+                Address::p2tr(&secp, internal_key, None, Network::Bitcoin),
+                MessageSignatureFormat::Bip322Simple,
+            ),
+        ] {
+            let signed_message =
+                sign_message(&master_xpriv, &path, &address, "Hello World").unwrap();
+            assert_eq!(signed_message.format, format);
+            assert_eq!(
+                verify_message(&address, "Hello World", &signed_message.signature),
+                Ok(format)
+            );
+            assert!(verify_message(&address, "Hello Word", &signed_message.signature).is_err());
+        }
+        let other_address = Address::p2wpkh(
+            &PublicKey::new(
+                master_xpriv
+                    .derive_priv(&secp, &DerivationPath::from_str("m/84'/0'/0'/0/4").unwrap())
+                    .unwrap()
+                    .to_keypair(&secp)
+                    .public_key(),
+            ),
+            Network::Bitcoin,
+        )
+        .unwrap();
+        assert_eq!(
+            sign_message(&master_xpriv, &path, &other_address, "Hello World"),
+            Err(MessageSigningProblem::KeyDoesNotMatchAddress)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt::Display;
+
+use bip39::{Language, Mnemonic};
+
+use crate::inputs::secret::SecretString;
+
+/// The word counts a BIP39 mnemonic can have.
+pub const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+const DEFAULT_MNEMONIC_WORD_COUNT: usize = 12;
+
+/// How many suggestions are offered for a partly typed word.
+const MAX_WORD_SUGGESTIONS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordState {
+    Empty,
+    /// The beginning of at least one BIP39 word.
+    Partial,
+    Valid,
+    /// Not a BIP39 word, nor the beginning of one.
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumState {
+    /// Some words are still missing or not valid.
+    Incomplete,
+    Valid,
+    Invalid,
+}
+
+impl Display for ChecksumState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChecksumState::Incomplete => write!(f, "checksum: waiting for all words"),
+            ChecksumState::Valid => write!(f, "checksum: valid"),
+            ChecksumState::Invalid => write!(f, "checksum: invalid, a word is wrong or misplaced"),
+        }
+    }
+}
+
+/// A mnemonic entered word by word. Every word is a secret of its own and is wiped on drop.
+#[derive(Debug, Clone, Default)]
+pub struct MnemonicWordGrid {
+    words: Vec<SecretString>,
+    /// The word last edited, whose suggestions are shown.
+    pub active_word: Option<usize>,
+}
+
+impl MnemonicWordGrid {
+    pub fn new(word_count: usize) -> Self {
+        MnemonicWordGrid {
+            words: vec![SecretString::default(); word_count],
+            active_word: None,
+        }
+    }
+
+    /// Splits a typed mnemonic into a grid big enough for all its words.
+    pub fn from_phrase(phrase: &str) -> Self {
+        let typed_words = phrase.split_whitespace().collect::<Vec<_>>();
+        let word_count = MNEMONIC_WORD_COUNTS
+            .into_iter()
+            .find(|word_count| *word_count >= typed_words.len())
+            .unwrap_or(typed_words.len())
+            .max(DEFAULT_MNEMONIC_WORD_COUNT);
+        let mut grid = MnemonicWordGrid::new(word_count);
+        for (index, word) in typed_words.into_iter().enumerate() {
+            grid.words[index] = SecretString::new(word.to_string());
+        }
+        grid
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Keeps the words already entered that still fit.
+    pub fn set_word_count(&mut self, word_count: usize) {
+        self.words.resize(word_count, SecretString::default());
+        self.active_word = self.active_word.filter(|index| *index < word_count);
+    }
+
+    pub fn word(&self, index: usize) -> &str {
+        self.words[index].expose_secret()
+    }
+
+    pub fn set_word(&mut self, index: usize, word: SecretString) {
+        self.words[index] = SecretString::new(word.expose_secret().trim().to_lowercase());
+        self.active_word = Some(index);
+    }
+
+    pub fn clear(&mut self) {
+        *self = MnemonicWordGrid::new(self.word_count());
+    }
+
+    /// Completes a partly typed word with its first suggestion. Returns whether the word is now valid.
+    pub fn complete_word(&mut self, index: usize) -> bool {
+        if let Some(suggestion) = self.suggestions(index).first() {
+            self.words[index] = SecretString::new(suggestion.to_string());
+        }
+        self.word_state(index) == WordState::Valid
+    }
+
+    pub fn suggestions(&self, index: usize) -> Vec<&'static str> {
+        let word = self.word(index);
+        if word.is_empty() {
+            return vec![];
+        }
+        Language::English
+            .words_by_prefix(word)
+            .iter()
+            .take(MAX_WORD_SUGGESTIONS)
+            .copied()
+            .collect()
+    }
+
+    pub fn word_state(&self, index: usize) -> WordState {
+        let word = self.word(index);
+        if word.is_empty() {
+            WordState::Empty
+        } else if Language::English.find_word(word).is_some() {
+            WordState::Valid
+        } else if !Language::English.words_by_prefix(word).is_empty() {
+            WordState::Partial
+        } else {
+            WordState::Invalid
+        }
+    }
+
+    /// The 1-based positions of the words that are not BIP39 words.
+    pub fn invalid_word_positions(&self) -> Vec<usize> {
+        (0..self.word_count())
+            .filter(|index| self.word_state(*index) == WordState::Invalid)
+            .map(|index| index + 1)
+            .collect()
+    }
+
+    pub fn checksum_state(&self) -> ChecksumState {
+        if (0..self.word_count()).any(|index| self.word_state(index) != WordState::Valid) {
+            return ChecksumState::Incomplete;
+        }
+        match Mnemonic::parse_in_normalized(Language::English, self.phrase().expose_secret()) {
+            Ok(_) => ChecksumState::Valid,
+            Err(_) => ChecksumState::Invalid,
+        }
+    }
+
+    /// The words joined the way the single line mnemonic input expects them.
+    pub fn phrase(&self) -> SecretString {
+        SecretString::new(
+            self.words
+                .iter()
+                .map(|word| word.expose_secret())
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+}
@@ -0,0 +1,19 @@
+pub mod broadcast;
+pub mod core_wallet;
+pub mod descriptor_export;
+pub mod dump_catalog;
+pub mod dump_integrity;
+pub mod dump_metadata;
+pub mod dump_preflight;
+pub mod fee_estimates;
+pub mod finds;
+pub mod message_signing;
+pub mod mnemonic_entry;
+pub mod profiles;
+pub mod results_export;
+pub mod results_table;
+pub mod snapshot_coins;
+pub mod snapshot_header;
+pub mod split_workflow;
+pub mod sweep;
+pub mod wif_export;
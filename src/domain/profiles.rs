@@ -0,0 +1,247 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use bitceptron_retriever::error::RetrieverError;
+use config::{Config, File, FileFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::inputs::{
+    bitcoincore_client::BitcoincoreClientInput, explorer::ExplorerInput,
+    retriever_specific::RetrieverSpecificInput,
+};
+
+const PROFILES_DIR_NAME: &str = "bitceptron-retriever-gui/profiles";
+const PROFILE_EXTENSION: &str = "toml";
+
+/// Where profiles are kept, under the user's config dir.
+pub fn profiles_dir() -> Option<PathBuf> {
+    let config_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join("Library")
+                .join("Application Support")
+        })
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|config_home| !config_home.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    config_dir.map(|config_dir| config_dir.join(PROFILES_DIR_NAME))
+}
+
+/// Profile names become file names, so only letters, digits, `-` and `_` are allowed.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn profile_path(profiles_dir: &Path, name: &str) -> PathBuf {
+    profiles_dir.join(format!("{}.{}", name, PROFILE_EXTENSION))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitcoincoreClientProfile {
+    pub url: String,
+    pub rpc_port: String,
+    pub timeout_seconds: String,
+    pub cookie_path: String,
+}
+
+impl Default for BitcoincoreClientProfile {
+    fn default() -> Self {
+        BitcoincoreClientProfile::from_input(&BitcoincoreClientInput::new())
+    }
+}
+
+impl BitcoincoreClientProfile {
+    fn from_input(input: &BitcoincoreClientInput) -> Self {
+        BitcoincoreClientProfile {
+            url: input.get_gui_url(),
+            rpc_port: input.get_gui_rpc_port(),
+            timeout_seconds: input.get_gui_timeout(),
+            cookie_path: input.get_gui_cookie_path(),
+        }
+    }
+
+    fn apply_to(&self, input: &mut BitcoincoreClientInput) {
+        input.set_url_from_gui_input(self.url.clone());
+        input.set_rpc_port_from_gui_input(self.rpc_port.clone());
+        input.set_timeout_from_gui_input(self.timeout_seconds.clone());
+        input.set_cookie_path_from_gui_input(self.cookie_path.clone());
+    }
+}
+
+/// The exploration settings without the mnemonic and the passphrase, which are never saved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExplorationProfile {
+    pub base_derivation_paths: String,
+    pub base_derivation_paths_from_presets: bool,
+    pub exploration_path: String,
+    pub exploration_depth: String,
+    pub network: String,
+    pub sweep: bool,
+}
+
+impl Default for ExplorationProfile {
+    fn default() -> Self {
+        ExplorationProfile::from_input(&ExplorerInput::new())
+    }
+}
+
+impl ExplorationProfile {
+    fn from_input(input: &ExplorerInput) -> Self {
+        ExplorationProfile {
+            base_derivation_paths: input.get_gui_base_derivation_paths(),
+            base_derivation_paths_from_presets: input.get_gui_base_derivation_paths_from_presets(),
+            exploration_path: input.get_gui_exploration_path(),
+            exploration_depth: input.get_gui_exploration_depth(),
+            network: input.get_gui_network().to_string(),
+            sweep: input.get_gui_sweep(),
+        }
+    }
+
+    fn apply_to(&self, input: &mut ExplorerInput) {
+        input.set_base_derivation_paths_from_gui_input(self.base_derivation_paths.clone());
+        input.set_base_derivation_paths_from_presets_from_gui_input(
+            self.base_derivation_paths_from_presets,
+        );
+        input.set_exploration_path_from_gui_input(self.exploration_path.clone());
+        input.set_exploration_depth_from_gui_input(self.exploration_depth.clone());
+        if let Ok(network) = bitcoin::Network::from_str(&self.network) {
+            input.set_network_from_gui_input(network);
+        }
+        input.set_sweep_from_gui_input(self.sweep);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetrieverProfile {
+    pub data_dir: String,
+    pub p2pk: bool,
+    pub p2pkh: bool,
+    pub p2wpkh: bool,
+    pub p2shwpkh: bool,
+    pub p2tr: bool,
+    pub verify_dump_hash: bool,
+    pub stale_dump_threshold: String,
+}
+
+impl Default for RetrieverProfile {
+    fn default() -> Self {
+        RetrieverProfile::from_input(&RetrieverSpecificInput::new())
+    }
+}
+
+impl RetrieverProfile {
+    fn from_input(input: &RetrieverSpecificInput) -> Self {
+        RetrieverProfile {
+            data_dir: input.get_gui_data_dir(),
+            p2pk: input.get_gui_p2pk(),
+            p2pkh: input.get_gui_p2pkh(),
+            p2wpkh: input.get_gui_p2wpkh(),
+            p2shwpkh: input.get_gui_p2shwpkh(),
+            p2tr: input.get_gui_p2tr(),
+            verify_dump_hash: input.get_gui_verify_dump_hash(),
+            stale_dump_threshold: input.get_gui_stale_dump_threshold(),
+        }
+    }
+
+    fn apply_to(&self, input: &mut RetrieverSpecificInput) {
+        input.set_data_dir_from_gui_input(self.data_dir.clone());
+        input.set_p2pk_inclusion_from_gui_input(self.p2pk);
+        input.set_p2pkh_inclusion_from_gui_input(self.p2pkh);
+        input.set_p2wpkh_inclusion_from_gui_input(self.p2wpkh);
+        input.set_p2shwpkh_inclusion_from_gui_input(self.p2shwpkh);
+        input.set_p2tr_inclusion_from_gui_input(self.p2tr);
+        input.set_verify_dump_hash_from_gui_input(self.verify_dump_hash);
+        input.set_stale_dump_threshold_from_gui_input(self.stale_dump_threshold.clone());
+    }
+}
+
+/// The non-secret settings typed in the gui, saved as one toml file per profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsProfile {
+    pub bitcoincore_client: BitcoincoreClientProfile,
+    pub exploration: ExplorationProfile,
+    pub retriever: RetrieverProfile,
+}
+
+impl SettingsProfile {
+    pub fn from_inputs(
+        bitcoincore_client_input: &BitcoincoreClientInput,
+        explorer_input: &ExplorerInput,
+        retriever_specific_input: &RetrieverSpecificInput,
+    ) -> Self {
+        SettingsProfile {
+            bitcoincore_client: BitcoincoreClientProfile::from_input(bitcoincore_client_input),
+            exploration: ExplorationProfile::from_input(explorer_input),
+            retriever: RetrieverProfile::from_input(retriever_specific_input),
+        }
+    }
+
+    /// Fills the gui inputs. They still have to be fixed before use.
+    pub fn apply_to(
+        &self,
+        bitcoincore_client_input: &mut BitcoincoreClientInput,
+        explorer_input: &mut ExplorerInput,
+        retriever_specific_input: &mut RetrieverSpecificInput,
+    ) {
+        self.bitcoincore_client.apply_to(bitcoincore_client_input);
+        self.exploration.apply_to(explorer_input);
+        self.retriever.apply_to(retriever_specific_input);
+    }
+
+    pub fn load(profiles_dir: &Path, name: &str) -> Result<Self, RetrieverError> {
+        let to_io_error =
+            |e: config::ConfigError| RetrieverError::IoError(std::io::Error::other(e));
+        Config::builder()
+            .add_source(File::from(profile_path(profiles_dir, name)).format(FileFormat::Toml))
+            .build()
+            .map_err(to_io_error)?
+            .try_deserialize()
+            .map_err(to_io_error)
+    }
+
+    pub fn save(&self, profiles_dir: &Path, name: &str) -> Result<PathBuf, RetrieverError> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?;
+        fs::create_dir_all(profiles_dir)?;
+        let path = profile_path(profiles_dir, name);
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+}
+
+/// The names of the saved profiles, sorted.
+pub fn list_profiles(profiles_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(profiles_dir) else {
+        return vec![];
+    };
+    let mut names = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == PROFILE_EXTENSION)
+        })
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .filter(|name| is_valid_profile_name(name))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
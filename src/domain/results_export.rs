@@ -0,0 +1,253 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use bitceptron_retriever::error::RetrieverError;
+use bitcoin::Network;
+use bitcoincore_rpc::jsonrpc::serde_json;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::view_elements::final_finds::FinalFinds;
+
+use super::{
+    dump_catalog::DumpFileEntry, finds::PathDescriptorFind, results_table::script_type_name,
+};
+
+pub const EXPORTS_DIR_NAME: &str = "exports";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "CSV"),
+            ExportFormat::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+/// A single find as it appears in an exported report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedFind {
+    pub result_num: u64,
+    pub path: String,
+    pub descriptor: String,
+    pub address: Option<String>,
+    pub script_type: Option<String>,
+    /// `None` when the details of the finds have not been fetched.
+    pub amount_in_sat: Option<u64>,
+    pub outpoints: Vec<String>,
+}
+
+/// Where the results came from, recorded in every report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ResultsSource {
+    pub network: String,
+    pub dump_file: Option<String>,
+    pub dump_height: Option<u64>,
+    pub dump_hash: Option<String>,
+}
+
+impl ResultsSource {
+    pub fn new(network: Network, selected_dump: Option<&DumpFileEntry>) -> Self {
+        ResultsSource {
+            network: network.to_string(),
+            dump_file: selected_dump.map(|dump| dump.path_string()),
+            dump_height: selected_dump.and_then(|dump| dump.height()),
+            dump_hash: selected_dump
+                .and_then(|dump| dump.metadata.as_ref())
+                .map(|metadata| metadata.base_hash.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResultsReport {
+    pub created_at: String,
+    pub source: ResultsSource,
+    pub has_details: bool,
+    pub finds: Vec<ExportedFind>,
+}
+
+impl ResultsReport {
+    pub fn from_final_finds(final_finds: &[FinalFinds], source: ResultsSource) -> Self {
+        ResultsReport {
+            created_at: Utc::now().to_rfc3339(),
+            source,
+            has_details: true,
+            finds: final_finds
+                .iter()
+                .map(|final_find| ExportedFind {
+                    result_num: final_find.get_result_num(),
+                    path: final_find.get_path().to_string(),
+                    descriptor: final_find.get_descriptor().to_string(),
+                    address: final_find.get_address().map(|address| address.to_string()),
+                    script_type: final_find
+                        .get_script_type()
+                        .map(|script_type| script_type_name(&script_type).to_string()),
+                    amount_in_sat: Some(final_find.get_amount_in_sat()),
+                    outpoints: final_find
+                        .get_unspents()
+                        .iter()
+                        .map(|utxo| utxo.out_point.to_string())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// A report of finds whose details are missing, so without amounts and outpoints.
+    pub fn from_finds(
+        finds: &[PathDescriptorFind],
+        network: Network,
+        source: ResultsSource,
+    ) -> Self {
+        ResultsReport {
+            created_at: Utc::now().to_rfc3339(),
+            source,
+            has_details: false,
+            finds: finds
+                .iter()
+                .enumerate()
+                .map(|(index, find)| ExportedFind {
+                    result_num: (index + 1) as u64,
+                    path: find.path.to_string(),
+                    descriptor: find.descriptor.to_string(),
+                    address: find
+                        .descriptor
+                        .address(network)
+                        .ok()
+                        .map(|address| address.to_string()),
+                    script_type: find
+                        .script_type()
+                        .map(|script_type| script_type_name(&script_type).to_string()),
+                    amount_in_sat: None,
+                    outpoints: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, RetrieverError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))
+    }
+
+    /// One row per find. The source of the results is repeated on every row so each row stands on its own in a spreadsheet.
+    pub fn to_csv(&self) -> Result<String, RetrieverError> {
+        let to_io_error = |e: csv::Error| RetrieverError::IoError(std::io::Error::other(e));
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer
+            .write_record([
+                "result",
+                "path",
+                "script_type",
+                "address",
+                "amount_in_sat",
+                "utxo_count",
+                "outpoints",
+                "descriptor",
+                "network",
+                "dump_height",
+                "dump_hash",
+            ])
+            .map_err(to_io_error)?;
+        for find in self.finds.iter() {
+            writer
+                .write_record([
+                    find.result_num.to_string(),
+                    find.path.clone(),
+                    find.script_type.clone().unwrap_or_default(),
+                    find.address.clone().unwrap_or_default(),
+                    find.amount_in_sat
+                        .map(|amount| amount.to_string())
+                        .unwrap_or_default(),
+                    if self.has_details {
+                        find.outpoints.len().to_string()
+                    } else {
+                        "".to_string()
+                    },
+                    find.outpoints.join(" "),
+                    find.descriptor.clone(),
+                    self.source.network.clone(),
+                    self.source
+                        .dump_height
+                        .map(|height| height.to_string())
+                        .unwrap_or_default(),
+                    self.source.dump_hash.clone().unwrap_or_default(),
+                ])
+                .map_err(to_io_error)?;
+        }
+        let content = writer
+            .into_inner()
+            .map_err(|e| RetrieverError::IoError(std::io::Error::other(e.to_string())))?;
+        Ok(String::from_utf8_lossy(&content).to_string())
+    }
+
+    /// Writes the report to `{data_dir}/exports/retriever_results_{time}.{extension}`.
+    pub fn write(&self, data_dir: &str, format: ExportFormat) -> Result<PathBuf, RetrieverError> {
+        let content = match format {
+            ExportFormat::Csv => self.to_csv()?,
+            ExportFormat::Json => self.to_json()?,
+        };
+        let export_path = export_file_path(data_dir, "retriever_results", format.extension());
+        write_export_file(&export_path, &content)?;
+        Ok(export_path)
+    }
+}
+
+pub fn export_file_path(data_dir: &str, prefix: &str, extension: &str) -> PathBuf {
+    let mut export_path = PathBuf::from(data_dir);
+    export_path.push(EXPORTS_DIR_NAME);
+    export_path.push(format!(
+        "{}_{}.{}",
+        prefix,
+        Utc::now().format("%Y%m%d_%H%M%S"),
+        extension
+    ));
+    export_path
+}
+
+pub fn write_export_file(export_path: &Path, content: &str) -> Result<(), RetrieverError> {
+    if let Some(exports_dir) = export_path.parent() {
+        fs::create_dir_all(exports_dir)?;
+    }
+    fs::write(export_path, content)?;
+    Ok(())
+}
+
+/// Writes a file holding keys. It is created only readable by the owner before any byte is written, and an
+/// existing file is never overwritten.
+pub fn write_private_export_file(export_path: &Path, content: &str) -> Result<(), RetrieverError> {
+    if let Some(exports_dir) = export_path.parent() {
+        fs::create_dir_all(exports_dir)?;
+    }
+    let mut open_options = fs::OpenOptions::new();
+    open_options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.mode(0o600);
+    }
+    let mut file = open_options.open(export_path)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    Ok(())
+}
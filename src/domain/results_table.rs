@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+use bitceptron_retriever::covered_descriptors::CoveredDescriptors;
+
+use crate::view_elements::final_finds::FinalFinds;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultsSortColumn {
+    #[default]
+    Index,
+    Path,
+    ScriptType,
+    Address,
+    Amount,
+    UtxoCount,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ScriptTypeFilter {
+    #[default]
+    All,
+    Only(CoveredDescriptors),
+}
+
+impl ScriptTypeFilter {
+    pub const ALL: [ScriptTypeFilter; 6] = [
+        ScriptTypeFilter::All,
+        ScriptTypeFilter::Only(CoveredDescriptors::P2pk),
+        ScriptTypeFilter::Only(CoveredDescriptors::P2pkh),
+        ScriptTypeFilter::Only(CoveredDescriptors::P2wpkh),
+        ScriptTypeFilter::Only(CoveredDescriptors::P2shwpkh),
+        ScriptTypeFilter::Only(CoveredDescriptors::P2tr),
+    ];
+}
+
+impl Display for ScriptTypeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptTypeFilter::All => write!(f, "all"),
+            ScriptTypeFilter::Only(script_type) => write!(f, "{}", script_type_name(script_type)),
+        }
+    }
+}
+
+pub fn script_type_name(script_type: &CoveredDescriptors) -> &'static str {
+    match script_type {
+        CoveredDescriptors::P2pk => "p2pk",
+        CoveredDescriptors::P2pkh => "p2pkh",
+        CoveredDescriptors::P2wpkh => "p2wpkh",
+        CoveredDescriptors::P2shwpkh => "p2sh-p2wpkh",
+        CoveredDescriptors::P2tr => "p2tr",
+    }
+}
+
+/// How the results table is sorted and filtered.
+#[derive(Debug, Clone, Default)]
+pub struct ResultsTable {
+    pub sort_column: ResultsSortColumn,
+    pub sort_descending: bool,
+    pub script_type_filter: ScriptTypeFilter,
+    pub min_amount_input: String,
+}
+
+impl ResultsTable {
+    /// Sorting by the current column again flips the order.
+    pub fn sort_by(&mut self, column: ResultsSortColumn) {
+        if self.sort_column == column {
+            self.sort_descending = !self.sort_descending;
+        } else {
+            self.sort_column = column;
+            self.sort_descending = false;
+        }
+    }
+
+    /// `None` when the input is empty or not a number of sats.
+    pub fn min_amount_in_sat(&self) -> Option<u64> {
+        self.min_amount_input.trim().replace(',', "").parse().ok()
+    }
+
+    pub fn is_min_amount_sane(&self) -> bool {
+        self.min_amount_input.trim().is_empty() || self.min_amount_in_sat().is_some()
+    }
+
+    pub fn visible_rows<'a>(&self, final_finds: &'a [FinalFinds]) -> Vec<&'a FinalFinds> {
+        let min_amount = self.min_amount_in_sat().unwrap_or(0);
+        let mut rows: Vec<&FinalFinds> = final_finds
+            .iter()
+            .filter(|final_find| match &self.script_type_filter {
+                ScriptTypeFilter::All => true,
+                ScriptTypeFilter::Only(script_type) => {
+                    final_find.get_script_type().as_ref() == Some(script_type)
+                }
+            })
+            .filter(|final_find| final_find.get_amount_in_sat() >= min_amount)
+            .collect();
+        rows.sort_by(|a, b| {
+            let ordering = match self.sort_column {
+                ResultsSortColumn::Index => a.get_result_num().cmp(&b.get_result_num()),
+                ResultsSortColumn::Path => a.get_path().cmp(b.get_path()),
+                ResultsSortColumn::ScriptType => a
+                    .get_script_type()
+                    .as_ref()
+                    .map(script_type_name)
+                    .cmp(&b.get_script_type().as_ref().map(script_type_name)),
+                ResultsSortColumn::Address => a.get_address_string().cmp(&b.get_address_string()),
+                ResultsSortColumn::Amount => a.get_amount_in_sat().cmp(&b.get_amount_in_sat()),
+                ResultsSortColumn::UtxoCount => a.get_unspents().len().cmp(&b.get_unspents().len()),
+            };
+            if self.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        rows
+    }
+}
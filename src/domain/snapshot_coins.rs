@@ -0,0 +1,137 @@
+use std::{
+    fs::File,
+    io::{BufReader, Seek, SeekFrom},
+    path::Path,
+};
+
+use bitceptron_retriever::error::RetrieverError;
+use bitcoin::{
+    consensus::{encode::VarInt as CompactSize, Decodable},
+    hashes::Hash,
+    OutPoint, Txid,
+};
+use tracing::error;
+
+use crate::domain::snapshot_header::{SnapshotFormat, SnapshotHeader, VERSIONED_HEADER_SIZE};
+
+/// The coins of a utxo snapshot file of either format, yielded the way `txoutset` yields legacy ones.
+pub enum SnapshotCoins {
+    Legacy(txoutset::Dump),
+    Versioned(VersionedSnapshotCoins),
+}
+
+impl SnapshotCoins {
+    pub fn open(path: &Path) -> Result<Self, RetrieverError> {
+        let header = SnapshotHeader::read(path)?;
+        if !header.is_supported() {
+            return Err(RetrieverError::ConsensusEncodeError(
+                bitcoin::consensus::encode::Error::ParseFailed("unsupported snapshot format"),
+            ));
+        }
+        match header.format {
+            SnapshotFormat::Legacy => Ok(SnapshotCoins::Legacy(txoutset::Dump::new(
+                path,
+                txoutset::ComputeAddresses::No,
+            )?)),
+            SnapshotFormat::Versioned { .. } => Ok(SnapshotCoins::Versioned(
+                VersionedSnapshotCoins::open(path, header.coin_count)?,
+            )),
+        }
+    }
+
+    pub fn coin_count(&self) -> u64 {
+        match self {
+            SnapshotCoins::Legacy(dump) => dump.utxo_set_size,
+            SnapshotCoins::Versioned(coins) => coins.coin_count,
+        }
+    }
+}
+
+impl Iterator for SnapshotCoins {
+    type Item = txoutset::TxOut;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            SnapshotCoins::Legacy(dump) => dump.next(),
+            SnapshotCoins::Versioned(coins) => coins.next(),
+        }
+    }
+}
+
+/// The coins of a versioned snapshot, grouped by txid: the txid, the number of its coins, then every coin as its
+/// vout followed by the same serialization as in legacy dumps.
+pub struct VersionedSnapshotCoins {
+    reader: BufReader<File>,
+    coin_count: u64,
+    coins_left: u64,
+    txid: Txid,
+    coins_left_in_txid: u64,
+}
+
+impl VersionedSnapshotCoins {
+    fn open(path: &Path, coin_count: u64) -> Result<Self, RetrieverError> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(VERSIONED_HEADER_SIZE))?;
+        Ok(VersionedSnapshotCoins {
+            reader: BufReader::new(file),
+            coin_count,
+            coins_left: coin_count,
+            txid: Txid::all_zeros(),
+            coins_left_in_txid: 0,
+        })
+    }
+
+    fn read_coin(&mut self) -> Result<txoutset::TxOut, bitcoin::consensus::encode::Error> {
+        if self.coins_left_in_txid == 0 {
+            self.txid = Txid::consensus_decode(&mut self.reader)?;
+            self.coins_left_in_txid = CompactSize::consensus_decode(&mut self.reader)?.0;
+            if self.coins_left_in_txid == 0 {
+                return Err(bitcoin::consensus::encode::Error::ParseFailed(
+                    "txid without coins in the snapshot",
+                ));
+            }
+        }
+        let vout = u32::try_from(CompactSize::consensus_decode(&mut self.reader)?.0)
+            .map_err(|_| bitcoin::consensus::encode::Error::ParseFailed("vout out of range"))?;
+        let code = u32::try_from(u64::from(txoutset::VarInt::consensus_decode(
+            &mut self.reader,
+        )?))
+        .map_err(|_| bitcoin::consensus::encode::Error::ParseFailed("invalid cast to u32"))?;
+        let amount = txoutset::Amount::consensus_decode(&mut self.reader)?;
+        let script_pubkey = txoutset::Script::consensus_decode(&mut self.reader)?.into_inner();
+        self.coins_left_in_txid -= 1;
+        Ok(txoutset::TxOut {
+            address: None,
+            amount,
+            height: code >> 1,
+            is_coinbase: code & 0x01 == 1,
+            out_point: OutPoint::new(self.txid, vout),
+            script_pubkey,
+        })
+    }
+}
+
+impl Iterator for VersionedSnapshotCoins {
+    type Item = txoutset::TxOut;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.coins_left == 0 {
+            return None;
+        }
+        match self.read_coin() {
+            Ok(txout) => {
+                self.coins_left -= 1;
+                Some(txout)
+            }
+            Err(e) => {
+                error!(
+                    "Coin {} of the snapshot could not be decoded: {:?}",
+                    self.coin_count - self.coins_left + 1,
+                    e
+                );
+                self.coins_left = 0;
+                None
+            }
+        }
+    }
+}
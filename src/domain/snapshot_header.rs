@@ -0,0 +1,137 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use bitceptron_retriever::error::RetrieverError;
+use bitcoin::{consensus::Decodable, p2p::Magic, BlockHash, Network};
+use num_format::{Locale, ToFormattedString};
+
+/// Magic bytes at the start of the versioned snapshot format introduced with assumeutxo.
+pub const SNAPSHOT_MAGIC_BYTES: [u8; 5] = [b'u', b't', b'x', b'o', 0xff];
+/// The versioned snapshot format that can be populated from, as written by `dumptxoutset` since bitcoincore 28.
+pub const SUPPORTED_SNAPSHOT_VERSION: u16 = 2;
+/// Smallest possible serialized coin: 36 bytes of outpoint and a byte each for code, amount and script.
+pub const MIN_SERIALIZED_COIN_SIZE: u64 = 39;
+/// Smallest possible coin in a versioned snapshot, where the txid is shared by the coins grouped under it: a byte
+/// each for vout, code, amount and script.
+pub const MIN_SERIALIZED_GROUPED_COIN_SIZE: u64 = 4;
+const LEGACY_HEADER_SIZE: u64 = 32 + 8;
+pub const VERSIONED_HEADER_SIZE: u64 = 5 + 2 + 4 + 32 + 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Block hash and coin count only, as written by `dumptxoutset` before the assumeutxo snapshot format.
+    Legacy,
+    /// Magic, version and network magic in front of the block hash and coin count.
+    Versioned { version: u16 },
+}
+
+/// The header of a utxo snapshot file, read before committing to populating from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    pub format: SnapshotFormat,
+    pub network_magic: Option<[u8; 4]>,
+    pub block_hash: BlockHash,
+    pub coin_count: u64,
+    pub file_size_in_bytes: u64,
+}
+
+impl SnapshotHeader {
+    pub fn read(path: &Path) -> Result<Self, RetrieverError> {
+        let file_size_in_bytes = std::fs::metadata(path)?.len();
+        let mut file = File::open(path)?;
+        let mut magic = [0u8; 5];
+        file.read_exact(&mut magic)?;
+        if magic == SNAPSHOT_MAGIC_BYTES {
+            let version = u16::consensus_decode(&mut file)?;
+            let mut network_magic = [0u8; 4];
+            file.read_exact(&mut network_magic)?;
+            let block_hash = BlockHash::consensus_decode(&mut file)?;
+            let coin_count = u64::consensus_decode(&mut file)?;
+            Ok(SnapshotHeader {
+                format: SnapshotFormat::Versioned { version },
+                network_magic: Some(network_magic),
+                block_hash,
+                coin_count,
+                file_size_in_bytes,
+            })
+        } else {
+            let mut dump = txoutset::Dump::new(path, txoutset::ComputeAddresses::No)?;
+            let block_hash = dump.block_hash;
+            let coin_count = dump.utxo_set_size;
+            if coin_count > 0 && dump.next().is_none() {
+                return Err(RetrieverError::ConsensusEncodeError(
+                    bitcoin::consensus::encode::Error::ParseFailed(
+                        "first coin of the snapshot could not be decoded",
+                    ),
+                ));
+            }
+            Ok(SnapshotHeader {
+                format: SnapshotFormat::Legacy,
+                network_magic: None,
+                block_hash,
+                coin_count,
+                file_size_in_bytes,
+            })
+        }
+    }
+
+    pub fn network(&self) -> Option<Network> {
+        self.network_magic
+            .and_then(|magic| Network::from_magic(Magic::from_bytes(magic)))
+    }
+
+    /// Whether the file is too small to hold the number of coins its header claims.
+    pub fn looks_truncated(&self) -> bool {
+        let (header_size, min_coin_size) = match self.format {
+            SnapshotFormat::Legacy => (LEGACY_HEADER_SIZE, MIN_SERIALIZED_COIN_SIZE),
+            SnapshotFormat::Versioned { .. } => {
+                (VERSIONED_HEADER_SIZE, MIN_SERIALIZED_GROUPED_COIN_SIZE)
+            }
+        };
+        self.file_size_in_bytes < header_size + self.coin_count * min_coin_size
+    }
+
+    /// Legacy dumps and versioned snapshots of the supported version can be populated from.
+    pub fn is_supported(&self) -> bool {
+        match self.format {
+            SnapshotFormat::Legacy => true,
+            SnapshotFormat::Versioned { version } => version == SUPPORTED_SNAPSHOT_VERSION,
+        }
+    }
+}
+
+impl Display for SnapshotHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "format: {} | magic: {} | network: {} | block: {} | coins: {} | size: {} MB",
+            match self.format {
+                SnapshotFormat::Legacy => "legacy dumptxoutset".to_string(),
+                SnapshotFormat::Versioned { version } => format!("snapshot v{}", version),
+            },
+            match self.network_magic {
+                Some(magic) => hex::encode(magic),
+                None => "none".to_string(),
+            },
+            match (self.network_magic, self.network()) {
+                (_, Some(network)) => network.to_string(),
+                (Some(_), None) => "unknown (custom signet?)".to_string(),
+                (None, None) => "not recorded".to_string(),
+            },
+            self.block_hash,
+            self.coin_count.to_formatted_string(&Locale::en),
+            (self.file_size_in_bytes / 1_000_000).to_formatted_string(&Locale::en),
+        )
+    }
+}
+
+/// A snapshot file the user pointed at, waiting to be used for populating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InspectedSnapshot {
+    pub path: PathBuf,
+    pub header: SnapshotHeader,
+}
@@ -0,0 +1,217 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use bitceptron_retriever::{covered_descriptors::CoveredDescriptors, error::RetrieverError};
+use bitcoin::{
+    hashes::{sha256, Hash},
+    Address, Network, PublicKey, Script,
+};
+use miniscript::Descriptor;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    results_export::{export_file_path, write_export_file},
+    results_table::script_type_name,
+};
+
+/// The first line of a derived scripts file, followed by the network it was derived for.
+const DERIVED_SCRIPTS_NETWORK_PREFIX: &str = "# retriever derived scripts, network: ";
+
+/// The descriptor of a pubkey for one of the covered script types.
+pub fn descriptor_for(
+    script_type: &CoveredDescriptors,
+    public_key: PublicKey,
+) -> Result<Descriptor<PublicKey>, RetrieverError> {
+    Ok(match script_type {
+        CoveredDescriptors::P2pk => Descriptor::new_pk(public_key),
+        CoveredDescriptors::P2pkh => Descriptor::new_pkh(public_key)?,
+        CoveredDescriptors::P2wpkh => Descriptor::new_wpkh(public_key)?,
+        CoveredDescriptors::P2shwpkh => Descriptor::new_sh_wpkh(public_key)?,
+        CoveredDescriptors::P2tr => Descriptor::new_tr(public_key, None)?,
+    })
+}
+
+pub fn script_sha256(script_pubkey: &[u8]) -> [u8; 32] {
+    sha256::Hash::hash(script_pubkey).to_byte_array()
+}
+
+/// One derived script. Only the hash of the script pubkey is kept, so the file tells nothing about the keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivedScriptRecord {
+    pub script_sha256: String,
+    pub path: String,
+    pub script_type: String,
+}
+
+impl DerivedScriptRecord {
+    pub fn new(script_pubkey: &Script, path: String, script_type: &CoveredDescriptors) -> Self {
+        DerivedScriptRecord {
+            script_sha256: hex::encode(script_sha256(script_pubkey.as_bytes())),
+            path,
+            script_type: script_type_name(script_type).to_string(),
+        }
+    }
+}
+
+/// Streams derived scripts to a csv file in the exports directory, on the machine holding the mnemonic.
+pub struct DerivedScriptsWriter {
+    pub path: PathBuf,
+    writer: csv::Writer<BufWriter<File>>,
+    pub records_written: u64,
+}
+
+impl DerivedScriptsWriter {
+    pub fn create(data_dir: &str, network: Network) -> Result<Self, RetrieverError> {
+        let path = export_file_path(data_dir, "derived_scripts", "csv");
+        write_export_file(
+            &path,
+            &format!("{}{}\n", DERIVED_SCRIPTS_NETWORK_PREFIX, network),
+        )?;
+        let file = File::options().append(true).open(&path)?;
+        Ok(DerivedScriptsWriter {
+            path,
+            writer: csv::Writer::from_writer(BufWriter::new(file)),
+            records_written: 0,
+        })
+    }
+
+    pub fn write(&mut self, record: &DerivedScriptRecord) -> Result<(), RetrieverError> {
+        self.writer
+            .serialize(record)
+            .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<(PathBuf, u64), RetrieverError> {
+        self.writer.flush()?;
+        Ok((self.path, self.records_written))
+    }
+}
+
+/// What the offline derivation wrote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineDerivationSummary {
+    pub file_path: PathBuf,
+    pub paths_derived: u64,
+    pub scripts_written: u64,
+}
+
+/// A derived scripts file loaded on the online machine, keyed by script hash.
+#[derive(Debug, Clone)]
+pub struct DerivedScripts {
+    pub network: Network,
+    pub records: hashbrown::HashMap<[u8; 32], Vec<DerivedScriptRecord>>,
+    pub records_read: u64,
+}
+
+impl DerivedScripts {
+    pub fn read(file_path: &Path) -> Result<Self, RetrieverError> {
+        let to_io_error = |e: csv::Error| RetrieverError::IoError(std::io::Error::other(e));
+        let mut reader = BufReader::new(File::open(file_path)?);
+        let mut first_line = String::new();
+        reader.read_line(&mut first_line)?;
+        let network = first_line
+            .trim()
+            .strip_prefix(DERIVED_SCRIPTS_NETWORK_PREFIX)
+            .and_then(|network| Network::from_str(network).ok())
+            .ok_or(RetrieverError::ConsensusEncodeError(
+                bitcoin::consensus::encode::Error::ParseFailed(
+                    "not a derived scripts file of this app",
+                ),
+            ))?;
+        let mut records: hashbrown::HashMap<[u8; 32], Vec<DerivedScriptRecord>> =
+            hashbrown::HashMap::new();
+        let mut records_read = 0;
+        for record in csv::Reader::from_reader(reader).deserialize() {
+            let record: DerivedScriptRecord = record.map_err(to_io_error)?;
+            let mut script_hash = [0u8; 32];
+            hex::decode_to_slice(&record.script_sha256, &mut script_hash)
+                .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?;
+            records.entry(script_hash).or_default().push(record);
+            records_read += 1;
+        }
+        Ok(DerivedScripts {
+            network,
+            records,
+            records_read,
+        })
+    }
+
+    /// Hashes every unspent script pubkey and keeps the ones found in the file.
+    pub fn match_unspent_scripts(
+        &self,
+        unspent_scripts: &hashbrown::HashSet<Vec<u8>>,
+    ) -> Vec<OnlineMatch> {
+        let mut matches: Vec<OnlineMatch> = unspent_scripts
+            .iter()
+            .par_bridge()
+            .filter_map(|script_pubkey| {
+                let records = self.records.get(&script_sha256(script_pubkey))?;
+                let script = Script::from_bytes(script_pubkey);
+                let address = Address::from_script(script, self.network)
+                    .ok()
+                    .map(|address| address.to_string());
+                Some(
+                    records
+                        .iter()
+                        .map(|record| OnlineMatch {
+                            path: record.path.clone(),
+                            script_type: record.script_type.clone(),
+                            address: address.clone(),
+                            script_pubkey: hex::encode(script_pubkey),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .flatten()
+            .collect();
+        matches.sort_by(|a, b| a.path.cmp(&b.path));
+        matches
+    }
+}
+
+/// A derived script found among the unspents, as reported back to the offline machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OnlineMatch {
+    pub path: String,
+    pub script_type: String,
+    pub address: Option<String>,
+    pub script_pubkey: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnlineMatchReport {
+    pub derived_scripts_file: PathBuf,
+    pub network: Network,
+    pub scripts_in_file: u64,
+    pub matches: Vec<OnlineMatch>,
+    pub matches_file: PathBuf,
+}
+
+pub fn write_online_matches(
+    matches: &[OnlineMatch],
+    data_dir: &str,
+) -> Result<PathBuf, RetrieverError> {
+    let to_io_error = |e: csv::Error| RetrieverError::IoError(std::io::Error::other(e));
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for online_match in matches.iter() {
+        writer.serialize(online_match).map_err(to_io_error)?;
+    }
+    if matches.is_empty() {
+        writer
+            .write_record(["path", "script_type", "address", "script_pubkey"])
+            .map_err(to_io_error)?;
+    }
+    let content = writer
+        .into_inner()
+        .map_err(|e| RetrieverError::IoError(std::io::Error::other(e.to_string())))?;
+    let matches_path = export_file_path(data_dir, "online_matches", "csv");
+    write_export_file(&matches_path, &String::from_utf8_lossy(&content))?;
+    Ok(matches_path)
+}
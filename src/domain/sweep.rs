@@ -0,0 +1,706 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use bitceptron_retriever::{covered_descriptors::CoveredDescriptors, error::RetrieverError};
+use bitcoin::{
+    absolute::LockTime,
+    bip32::{DerivationPath, Xpriv},
+    consensus::encode::serialize_hex,
+    ecdsa,
+    hashes::Hash,
+    key::{Keypair, Secp256k1, TapTweak},
+    psbt::Psbt,
+    script::{Builder, PushBytesBuf},
+    secp256k1::{All, Message},
+    sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType},
+    taproot,
+    transaction::Version,
+    Address, Amount, Network, OutPoint, PublicKey, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
+    Txid, WPubkeyHash, Witness,
+};
+use num_format::{Locale, ToFormattedString};
+
+use super::finds::{script_type_of, DetailedFind};
+
+/// Version and locktime.
+const TX_VERSION_AND_LOCKTIME_SIZE: u64 = 4 + 4;
+/// The segwit marker and flag, counted in weight units as they are witness data.
+const SEGWIT_MARKER_AND_FLAG_WEIGHT: u64 = 2;
+/// Outpoint, script_sig length and sequence of any input.
+const INPUT_BASE_SIZE: u64 = 36 + 1 + 4;
+/// A low-r der signature is at most 70 bytes, plus the sighash byte.
+const ECDSA_SIGNATURE_SIZE: u64 = 71;
+const SCHNORR_SIGNATURE_SIZE: u64 = 64;
+const COMPRESSED_PUBLIC_KEY_SIZE: u64 = 33;
+/// The `0014{20 byte key hash}` redeem script of a p2sh-p2wpkh input.
+const P2SHWPKH_REDEEM_SCRIPT_SIZE: u64 = 22;
+
+/// Size of a bitcoin compact size integer.
+fn compact_size_len(n: u64) -> u64 {
+    match n {
+        0..=0xfc => 1,
+        0xfd..=0xffff => 3,
+        0x10000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
+
+/// Weight of a signed input spending the given script type, with signatures at their largest.
+/// Legacy inputs in a segwit transaction also carry an empty witness, which is added by the caller.
+fn signed_input_weight(script_type: &CoveredDescriptors) -> u64 {
+    let push = |size: u64| 1 + size;
+    match script_type {
+        CoveredDescriptors::P2pk => (INPUT_BASE_SIZE + push(ECDSA_SIGNATURE_SIZE)) * 4,
+        CoveredDescriptors::P2pkh => {
+            (INPUT_BASE_SIZE + push(ECDSA_SIGNATURE_SIZE) + push(COMPRESSED_PUBLIC_KEY_SIZE)) * 4
+        }
+        CoveredDescriptors::P2wpkh => {
+            INPUT_BASE_SIZE * 4 + 1 + push(ECDSA_SIGNATURE_SIZE) + push(COMPRESSED_PUBLIC_KEY_SIZE)
+        }
+        CoveredDescriptors::P2shwpkh => {
+            (INPUT_BASE_SIZE + push(P2SHWPKH_REDEEM_SCRIPT_SIZE)) * 4
+                + 1
+                + push(ECDSA_SIGNATURE_SIZE)
+                + push(COMPRESSED_PUBLIC_KEY_SIZE)
+        }
+        CoveredDescriptors::P2tr => INPUT_BASE_SIZE * 4 + 1 + push(SCHNORR_SIGNATURE_SIZE),
+    }
+}
+
+/// Virtual size of the signed sweep of the given inputs to a single output.
+pub fn estimate_sweep_vsize(inputs: &[SweepInput], destination_script_len: u64) -> u64 {
+    let output_size = 8 + compact_size_len(destination_script_len) + destination_script_len;
+    let mut weight = (TX_VERSION_AND_LOCKTIME_SIZE
+        + compact_size_len(inputs.len() as u64)
+        + compact_size_len(1)
+        + output_size)
+        * 4;
+    weight += inputs
+        .iter()
+        .map(|input| signed_input_weight(&input.script_type))
+        .sum::<u64>();
+    if inputs.iter().any(|input| is_segwit(&input.script_type)) {
+        weight += SEGWIT_MARKER_AND_FLAG_WEIGHT;
+        weight += inputs
+            .iter()
+            .filter(|input| !is_segwit(&input.script_type))
+            .count() as u64;
+    }
+    weight.div_ceil(4)
+}
+
+fn is_segwit(script_type: &CoveredDescriptors) -> bool {
+    !matches!(
+        script_type,
+        CoveredDescriptors::P2pk | CoveredDescriptors::P2pkh
+    )
+}
+
+/// A found unspent to be swept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepInput {
+    pub out_point: OutPoint,
+    pub amount_in_sat: u64,
+    pub script_type: CoveredDescriptors,
+    pub script_pubkey: ScriptBuf,
+    pub path: DerivationPath,
+}
+
+impl SweepInput {
+    /// An outpoint found under more than one path is swept once, a transaction spending it twice is invalid.
+    pub fn from_detailed_finds(detailed_finds: &[DetailedFind]) -> Vec<Self> {
+        let mut seen_out_points = HashSet::new();
+        detailed_finds
+            .iter()
+            .filter_map(|detailed| {
+                let script_type = script_type_of(&detailed.descriptor)?;
+                Some(detailed.unspents.iter().map(move |utxo| SweepInput {
+                    out_point: utxo.out_point,
+                    amount_in_sat: utxo.amount_in_sat,
+                    script_type: script_type.clone(),
+                    script_pubkey: detailed.descriptor.script_pubkey(),
+                    path: detailed.path.clone(),
+                }))
+            })
+            .flatten()
+            .filter(|input| seen_out_points.insert(input.out_point))
+            .collect()
+    }
+
+    fn needs_previous_transaction(&self) -> bool {
+        !is_segwit(&self.script_type)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepProblem {
+    NoUnspents,
+    InvalidDestination(String),
+    InvalidFeeRate,
+    FeeExceedsTotal {
+        fee_in_sat: u64,
+        total_in_sat: u64,
+    },
+    DustOutput {
+        amount_in_sat: u64,
+        dust_limit_in_sat: u64,
+    },
+}
+
+impl Display for SweepProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepProblem::NoUnspents => write!(f, "There are no found unspents to sweep. Get the details of the finds first."),
+            SweepProblem::InvalidDestination(reason) => write!(f, "The destination address is not valid: {}", reason),
+            SweepProblem::InvalidFeeRate => write!(f, "The fee rate must be a positive number of sats per vbyte."),
+            SweepProblem::FeeExceedsTotal { fee_in_sat, total_in_sat } => write!(
+                f,
+                "The fee of {} sats is more than the {} sats being swept.",
+                fee_in_sat.to_formatted_string(&Locale::en),
+                total_in_sat.to_formatted_string(&Locale::en)
+            ),
+            SweepProblem::DustOutput { amount_in_sat, dust_limit_in_sat } => write!(
+                f,
+                "After fees only {} sats are left, below the dust limit of {} sats for the destination.",
+                amount_in_sat.to_formatted_string(&Locale::en),
+                dust_limit_in_sat.to_formatted_string(&Locale::en)
+            ),
+        }
+    }
+}
+
+/// Why a prepared sweep could not be signed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SweepSigningProblem {
+    KeyDerivation(String),
+    Sighash(String),
+    Finalization(String),
+}
+
+impl Display for SweepSigningProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepSigningProblem::KeyDerivation(reason) => {
+                write!(f, "The key of an input could not be derived: {}", reason)
+            }
+            SweepSigningProblem::Sighash(reason) => write!(
+                f,
+                "The sighash of an input could not be computed: {}",
+                reason
+            ),
+            SweepSigningProblem::Finalization(reason) => write!(
+                f,
+                "The signed transaction could not be finalized: {}",
+                reason
+            ),
+        }
+    }
+}
+
+/// Every found unspent going to a single destination, with the fee worked out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPlan {
+    pub inputs: Vec<SweepInput>,
+    pub destination: Address,
+    pub fee_rate_sat_per_vb: f64,
+    pub estimated_vsize: u64,
+    pub total_in_sat: u64,
+    pub fee_in_sat: u64,
+    pub amount_after_fee_in_sat: u64,
+}
+
+impl SweepPlan {
+    pub fn new(
+        inputs: Vec<SweepInput>,
+        destination_input: &str,
+        fee_rate_input: &str,
+        network: Network,
+    ) -> Result<Self, SweepProblem> {
+        if inputs.is_empty() {
+            return Err(SweepProblem::NoUnspents);
+        }
+        let destination = Address::from_str(destination_input.trim())
+            .map_err(|e| SweepProblem::InvalidDestination(e.to_string()))?
+            .require_network(network)
+            .map_err(|e| SweepProblem::InvalidDestination(e.to_string()))?;
+        let fee_rate_sat_per_vb = fee_rate_input
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|fee_rate| fee_rate.is_finite() && *fee_rate > 0.)
+            .ok_or(SweepProblem::InvalidFeeRate)?;
+        let estimated_vsize =
+            estimate_sweep_vsize(&inputs, destination.script_pubkey().len() as u64);
+        let fee_in_sat = (estimated_vsize as f64 * fee_rate_sat_per_vb).ceil() as u64;
+        let total_in_sat = inputs.iter().map(|input| input.amount_in_sat).sum::<u64>();
+        if fee_in_sat >= total_in_sat {
+            return Err(SweepProblem::FeeExceedsTotal {
+                fee_in_sat,
+                total_in_sat,
+            });
+        }
+        let amount_after_fee_in_sat = total_in_sat - fee_in_sat;
+        let dust_limit_in_sat = destination.script_pubkey().dust_value().to_sat();
+        if amount_after_fee_in_sat < dust_limit_in_sat {
+            return Err(SweepProblem::DustOutput {
+                amount_in_sat: amount_after_fee_in_sat,
+                dust_limit_in_sat,
+            });
+        }
+        Ok(SweepPlan {
+            inputs,
+            destination,
+            fee_rate_sat_per_vb,
+            estimated_vsize,
+            total_in_sat,
+            fee_in_sat,
+            amount_after_fee_in_sat,
+        })
+    }
+
+    /// Txids of the legacy inputs, whose full previous transaction belongs in the psbt.
+    pub fn txids_needing_previous_transaction(&self) -> Vec<Txid> {
+        let mut txids: Vec<Txid> = self
+            .inputs
+            .iter()
+            .filter(|input| input.needs_previous_transaction())
+            .map(|input| input.out_point.txid)
+            .collect();
+        txids.sort();
+        txids.dedup();
+        txids
+    }
+
+    /// An unsigned, rbf enabled psbt with the bip32 and taproot key origins of every input.
+    pub fn to_psbt(
+        &self,
+        master_xpriv: &Xpriv,
+        previous_transactions: &HashMap<Txid, Transaction>,
+    ) -> Result<Psbt, RetrieverError> {
+        let secp = Secp256k1::new();
+        let fingerprint = master_xpriv.fingerprint(&secp);
+        let unsigned_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: self
+                .inputs
+                .iter()
+                .map(|input| TxIn {
+                    previous_output: input.out_point,
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: vec![TxOut {
+                value: Amount::from_sat(self.amount_after_fee_in_sat),
+                script_pubkey: self.destination.script_pubkey(),
+            }],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(unsigned_tx).map_err(|_| {
+            RetrieverError::ConsensusEncodeError(bitcoin::consensus::encode::Error::ParseFailed(
+                "sweep transaction could not be turned into a psbt",
+            ))
+        })?;
+        for (psbt_input, input) in psbt.inputs.iter_mut().zip(self.inputs.iter()) {
+            let public_key = master_xpriv
+                .derive_priv(&secp, &input.path)?
+                .to_keypair(&secp)
+                .public_key();
+            let key_source = (fingerprint, input.path.clone());
+            // Segwit inputs carry the spent output, legacy ones the whole previous transaction, as bip174 says.
+            if input.needs_previous_transaction() {
+                psbt_input.non_witness_utxo =
+                    previous_transactions.get(&input.out_point.txid).cloned();
+            } else {
+                psbt_input.witness_utxo = Some(TxOut {
+                    value: Amount::from_sat(input.amount_in_sat),
+                    script_pubkey: input.script_pubkey.clone(),
+                });
+            }
+            match input.script_type {
+                CoveredDescriptors::P2tr => {
+                    let (x_only_public_key, _) = public_key.x_only_public_key();
+                    psbt_input.tap_internal_key = Some(x_only_public_key);
+                    psbt_input
+                        .tap_key_origins
+                        .insert(x_only_public_key, (vec![], key_source));
+                }
+                CoveredDescriptors::P2shwpkh => {
+                    let wpubkey_hash = WPubkeyHash::hash(&public_key.serialize());
+                    psbt_input.redeem_script = Some(ScriptBuf::new_p2wpkh(&wpubkey_hash));
+                    psbt_input.bip32_derivation.insert(public_key, key_source);
+                }
+                _ => {
+                    psbt_input.bip32_derivation.insert(public_key, key_source);
+                }
+            }
+        }
+        Ok(psbt)
+    }
+}
+
+/// A sweep psbt built from a plan and saved to the exports directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedSweep {
+    pub plan: SweepPlan,
+    pub psbt: Psbt,
+    pub psbt_path: PathBuf,
+    /// Legacy inputs whose previous transaction could not be fetched. Some signers refuse such inputs.
+    pub missing_previous_transactions: usize,
+}
+
+impl PreparedSweep {
+    /// Signs every input with keys derived on the spot and extracts the finalized transaction.
+    /// The derived keys never leave this function.
+    pub fn sign(&self, master_xpriv: &Xpriv) -> Result<Transaction, SweepSigningProblem> {
+        let secp = Secp256k1::new();
+        let mut psbt = self.psbt.clone();
+        let previous_outputs: Vec<TxOut> = self
+            .plan
+            .inputs
+            .iter()
+            .map(|input| TxOut {
+                value: Amount::from_sat(input.amount_in_sat),
+                script_pubkey: input.script_pubkey.clone(),
+            })
+            .collect();
+        let mut sighash_cache = SighashCache::new(&self.psbt.unsigned_tx);
+        for (input_index, (psbt_input, input)) in psbt
+            .inputs
+            .iter_mut()
+            .zip(self.plan.inputs.iter())
+            .enumerate()
+        {
+            let keypair = master_xpriv
+                .derive_priv(&secp, &input.path)
+                .map_err(|e| SweepSigningProblem::KeyDerivation(e.to_string()))?
+                .to_keypair(&secp);
+            let public_key = PublicKey::new(keypair.public_key());
+            match input.script_type {
+                CoveredDescriptors::P2pk | CoveredDescriptors::P2pkh => {
+                    let sighash = sighash_cache
+                        .legacy_signature_hash(
+                            input_index,
+                            &input.script_pubkey,
+                            EcdsaSighashType::All.to_u32(),
+                        )
+                        .map_err(to_sighash_problem)?;
+                    let signature = sign_ecdsa(&secp, &keypair, sighash.to_byte_array());
+                    let mut script_sig = Builder::new().push_slice(signature.serialize());
+                    if input.script_type == CoveredDescriptors::P2pkh {
+                        script_sig = script_sig.push_key(&public_key);
+                    }
+                    psbt_input.final_script_sig = Some(script_sig.into_script());
+                }
+                CoveredDescriptors::P2wpkh => {
+                    let sighash = sighash_cache
+                        .p2wpkh_signature_hash(
+                            input_index,
+                            &input.script_pubkey,
+                            Amount::from_sat(input.amount_in_sat),
+                            EcdsaSighashType::All,
+                        )
+                        .map_err(to_sighash_problem)?;
+                    let signature = sign_ecdsa(&secp, &keypair, sighash.to_byte_array());
+                    psbt_input.final_script_witness =
+                        Some(Witness::p2wpkh(&signature, &keypair.public_key()));
+                }
+                CoveredDescriptors::P2shwpkh => {
+                    let redeem_script =
+                        ScriptBuf::new_p2wpkh(&WPubkeyHash::hash(&public_key.to_bytes()));
+                    let sighash = sighash_cache
+                        .p2wpkh_signature_hash(
+                            input_index,
+                            &redeem_script,
+                            Amount::from_sat(input.amount_in_sat),
+                            EcdsaSighashType::All,
+                        )
+                        .map_err(to_sighash_problem)?;
+                    let signature = sign_ecdsa(&secp, &keypair, sighash.to_byte_array());
+                    let redeem_script_push = PushBytesBuf::try_from(redeem_script.to_bytes())
+                        .map_err(|e| SweepSigningProblem::Finalization(e.to_string()))?;
+                    psbt_input.final_script_sig =
+                        Some(Builder::new().push_slice(redeem_script_push).into_script());
+                    psbt_input.final_script_witness =
+                        Some(Witness::p2wpkh(&signature, &keypair.public_key()));
+                }
+                CoveredDescriptors::P2tr => {
+                    let sighash = sighash_cache
+                        .taproot_key_spend_signature_hash(
+                            input_index,
+                            &Prevouts::All(&previous_outputs),
+                            TapSighashType::Default,
+                        )
+                        .map_err(to_sighash_problem)?;
+                    let tweaked_keypair = keypair.tap_tweak(&secp, None).to_inner();
+                    let signature = taproot::Signature {
+                        sig: secp.sign_schnorr(
+                            &Message::from_digest(sighash.to_byte_array()),
+                            &tweaked_keypair,
+                        ),
+                        hash_ty: TapSighashType::Default,
+                    };
+                    let mut witness = Witness::new();
+                    witness.push(signature.to_vec());
+                    psbt_input.final_script_witness = Some(witness);
+                }
+            }
+        }
+        // The fee is the one of the plan. Checking it again needs the previous transaction of every legacy input,
+        // which may not have been fetched.
+        Ok(psbt.extract_tx_unchecked_fee_rate())
+    }
+}
+
+fn sign_ecdsa(secp: &Secp256k1<All>, keypair: &Keypair, digest: [u8; 32]) -> ecdsa::Signature {
+    ecdsa::Signature {
+        sig: secp.sign_ecdsa_low_r(&Message::from_digest(digest), &keypair.secret_key()),
+        hash_ty: EcdsaSighashType::All,
+    }
+}
+
+fn to_sighash_problem<E: Display>(e: E) -> SweepSigningProblem {
+    SweepSigningProblem::Sighash(e.to_string())
+}
+
+/// A signed sweep transaction, saved to the exports directory as hex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedSweep {
+    pub transaction: Transaction,
+    pub transaction_path: PathBuf,
+}
+
+impl SignedSweep {
+    pub fn txid(&self) -> Txid {
+        self.transaction.txid()
+    }
+
+    pub fn raw_hex(&self) -> String {
+        serialize_hex(&self.transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+    use bitceptron_retriever::covered_descriptors::CoveredDescriptors;
+    use bitcoin::{
+        bip32::{DerivationPath, Xpriv},
+        ecdsa,
+        hashes::Hash,
+        key::{Secp256k1, XOnlyPublicKey},
+        script::Instruction,
+        secp256k1::{All, Message},
+        sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType},
+        taproot, Amount, Network, OutPoint, PublicKey, ScriptBuf, Transaction, TxOut, Txid,
+        WPubkeyHash,
+    };
+
+    use super::{estimate_sweep_vsize, PreparedSweep, SweepInput, SweepPlan};
+
+    const DESTINATION: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+
+    fn script_pubkey_of(
+        secp: &Secp256k1<All>,
+        public_key: &PublicKey,
+        script_type: &CoveredDescriptors,
+    ) -> ScriptBuf {
+        let p2wpkh = ScriptBuf::new_p2wpkh(&WPubkeyHash::hash(&public_key.to_bytes()));
+        match script_type {
+            CoveredDescriptors::P2pk => ScriptBuf::new_p2pk(public_key),
+            CoveredDescriptors::P2pkh => ScriptBuf::new_p2pkh(&public_key.pubkey_hash()),
+            CoveredDescriptors::P2wpkh => p2wpkh,
+            CoveredDescriptors::P2shwpkh => p2wpkh.to_p2sh(),
+            CoveredDescriptors::P2tr => {
+                ScriptBuf::new_p2tr(secp, public_key.inner.x_only_public_key().0, None)
+            }
+        }
+    }
+
+    fn pushes(script: &ScriptBuf) -> Vec<Vec<u8>> {
+        script
+            .instructions()
+            .map(|instruction| match instruction.unwrap() {
+                Instruction::PushBytes(bytes) => bytes.as_bytes().to_vec(),
+                Instruction::Op(op) => panic!("{} in a script sig", op),
+            })
+            .collect()
+    }
+
+    fn verify_ecdsa(secp: &Secp256k1<All>, digest: [u8; 32], signature: &[u8], public_key: &[u8]) {
+        let signature = ecdsa::Signature::from_slice(signature).unwrap();
+        assert_eq!(signature.hash_ty, EcdsaSighashType::All);
+        secp.verify_ecdsa(
+            &Message::from_digest(digest),
+            &signature.sig,
+            &PublicKey::from_slice(public_key).unwrap().inner,
+        )
+        .unwrap();
+    }
+
+    /// Checks the spend of every input from the signed transaction alone, against sighashes computed anew.
+    fn verify_spend(
+        secp: &Secp256k1<All>,
+        transaction: &Transaction,
+        previous_outputs: &[TxOut],
+        script_types: &[CoveredDescriptors],
+    ) {
+        let mut sighash_cache = SighashCache::new(transaction);
+        for (index, (previous_output, script_type)) in
+            previous_outputs.iter().zip(script_types).enumerate()
+        {
+            let input = &transaction.input[index];
+            let script_pubkey = &previous_output.script_pubkey;
+            match script_type {
+                CoveredDescriptors::P2pk | CoveredDescriptors::P2pkh => {
+                    assert!(input.witness.is_empty());
+                    let script_sig_pushes = pushes(&input.script_sig);
+                    let public_key = if *script_type == CoveredDescriptors::P2pk {
+                        assert_eq!(script_sig_pushes.len(), 1);
+                        script_pubkey.as_bytes()[1..34].to_vec()
+                    } else {
+                        assert_eq!(script_sig_pushes.len(), 2);
+                        let public_key = script_sig_pushes[1].clone();
+                        assert_eq!(
+                            ScriptBuf::new_p2pkh(
+                                &PublicKey::from_slice(&public_key).unwrap().pubkey_hash()
+                            ),
+                            *script_pubkey
+                        );
+                        public_key
+                    };
+                    let sighash = sighash_cache
+                        .legacy_signature_hash(index, script_pubkey, EcdsaSighashType::All.to_u32())
+                        .unwrap();
+                    verify_ecdsa(
+                        secp,
+                        sighash.to_byte_array(),
+                        &script_sig_pushes[0],
+                        &public_key,
+                    );
+                }
+                CoveredDescriptors::P2wpkh | CoveredDescriptors::P2shwpkh => {
+                    let witness_program = if *script_type == CoveredDescriptors::P2wpkh {
+                        assert!(input.script_sig.is_empty());
+                        script_pubkey.clone()
+                    } else {
+                        let script_sig_pushes = pushes(&input.script_sig);
+                        assert_eq!(script_sig_pushes.len(), 1);
+                        let redeem_script = ScriptBuf::from_bytes(script_sig_pushes[0].clone());
+                        assert_eq!(redeem_script.to_p2sh(), *script_pubkey);
+                        redeem_script
+                    };
+                    assert_eq!(input.witness.len(), 2);
+                    let public_key = input.witness.nth(1).unwrap();
+                    assert_eq!(
+                        ScriptBuf::new_p2wpkh(&WPubkeyHash::hash(public_key)),
+                        witness_program
+                    );
+                    let sighash = sighash_cache
+                        .p2wpkh_signature_hash(
+                            index,
+                            &witness_program,
+                            previous_output.value,
+                            EcdsaSighashType::All,
+                        )
+                        .unwrap();
+                    verify_ecdsa(
+                        secp,
+                        sighash.to_byte_array(),
+                        input.witness.nth(0).unwrap(),
+                        public_key,
+                    );
+                }
+                CoveredDescriptors::P2tr => {
+                    assert!(input.script_sig.is_empty());
+                    assert_eq!(input.witness.len(), 1);
+                    let signature =
+                        taproot::Signature::from_slice(input.witness.nth(0).unwrap()).unwrap();
+                    assert_eq!(signature.hash_ty, TapSighashType::Default);
+                    let sighash = sighash_cache
+                        .taproot_key_spend_signature_hash(
+                            index,
+                            &Prevouts::All(previous_outputs),
+                            TapSighashType::Default,
+                        )
+                        .unwrap();
+                    secp.verify_schnorr(
+                        &signature.sig,
+                        &Message::from_digest(sighash.to_byte_array()),
+                        &XOnlyPublicKey::from_slice(&script_pubkey.as_bytes()[2..]).unwrap(),
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sweep_of_every_script_type_signs_to_valid_spends() {
+        let secp = Secp256k1::new();
+        let master_xpriv = Xpriv::new_master(Network::Bitcoin, &[7u8; 32]).unwrap();
+        let script_types = [
+            CoveredDescriptors::P2pk,
+            CoveredDescriptors::P2pkh,
+            CoveredDescriptors::P2wpkh,
+            CoveredDescriptors::P2shwpkh,
+            CoveredDescriptors::P2tr,
+        ];
+        let inputs: Vec<SweepInput> = script_types
+            .iter()
+            .enumerate()
+            .map(|(index, script_type)| {
+                let path = DerivationPath::from_str(&format!("m/0'/{}", index)).unwrap();
+                let public_key = PublicKey::new(
+                    master_xpriv
+                        .derive_priv(&secp, &path)
+                        .unwrap()
+                        .to_keypair(&secp)
+                        .public_key(),
+                );
+                SweepInput {
+                    out_point: OutPoint::new(Txid::from_byte_array([index as u8 + 1; 32]), 1),
+                    amount_in_sat: 20_000 + index as u64,
+                    script_type: script_type.clone(),
+                    script_pubkey: script_pubkey_of(&secp, &public_key, script_type),
+                    path,
+                }
+            })
+            .collect();
+        let plan = SweepPlan::new(inputs, DESTINATION, "2", Network::Bitcoin).unwrap();
+        let prepared_sweep = PreparedSweep {
+            psbt: plan.to_psbt(&master_xpriv, &HashMap::new()).unwrap(),
+            plan,
+            psbt_path: PathBuf::new(),
+            missing_previous_transactions: 0,
+        };
+        let transaction = prepared_sweep.sign(&master_xpriv).unwrap();
+
+        let previous_outputs: Vec<TxOut> = prepared_sweep
+            .plan
+            .inputs
+            .iter()
+            .map(|input| TxOut {
+                value: Amount::from_sat(input.amount_in_sat),
+                script_pubkey: input.script_pubkey.clone(),
+            })
+            .collect();
+        verify_spend(&secp, &transaction, &previous_outputs, &script_types);
+        assert_eq!(
+            transaction.output[0].value.to_sat(),
+            prepared_sweep.plan.amount_after_fee_in_sat
+        );
+        let estimated_vsize = estimate_sweep_vsize(
+            &prepared_sweep.plan.inputs,
+            transaction.output[0].script_pubkey.len() as u64,
+        );
+        assert_eq!(estimated_vsize, prepared_sweep.plan.estimated_vsize);
+        assert!(transaction.vsize() as u64 <= estimated_vsize);
+    }
+}
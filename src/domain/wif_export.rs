@@ -0,0 +1,87 @@
+use std::fmt::Debug;
+
+use bitceptron_retriever::{covered_descriptors::CoveredDescriptors, error::RetrieverError};
+use bitcoin::{
+    bip32::{DerivationPath, Xpriv},
+    key::Secp256k1,
+    Network,
+};
+use zeroize::Zeroizing;
+
+use super::finds::PathDescriptorFind;
+
+/// What the user has to type before any private key is shown.
+pub const WIF_EXPORT_CONFIRMATION_PHRASE: &str = "show my private keys";
+
+/// The prefix electrum expects in front of an imported key. P2pk and p2tr keys cannot be imported into electrum.
+pub fn electrum_script_type_prefix(script_type: &CoveredDescriptors) -> Option<&'static str> {
+    match script_type {
+        CoveredDescriptors::P2pkh => Some("p2pkh"),
+        CoveredDescriptors::P2wpkh => Some("p2wpkh"),
+        CoveredDescriptors::P2shwpkh => Some("p2wpkh-p2sh"),
+        CoveredDescriptors::P2pk | CoveredDescriptors::P2tr => None,
+    }
+}
+
+/// The private key of a find in wallet import format. The key is wiped from memory when dropped.
+pub struct WifKey {
+    pub path: DerivationPath,
+    pub script_type: Option<CoveredDescriptors>,
+    pub address: Option<String>,
+    electrum_key: Zeroizing<String>,
+}
+
+impl WifKey {
+    pub fn from_find(
+        find: &PathDescriptorFind,
+        master_xpriv: &Xpriv,
+        network: Network,
+    ) -> Result<Self, RetrieverError> {
+        let secp = Secp256k1::new();
+        let mut private_key = master_xpriv.derive_priv(&secp, &find.path)?.to_priv();
+        let wif = Zeroizing::new(private_key.to_wif());
+        private_key.inner.non_secure_erase();
+        let script_type = find.script_type();
+        let electrum_key = match script_type.as_ref().and_then(electrum_script_type_prefix) {
+            Some(prefix) => Zeroizing::new(format!("{}:{}", prefix, wif.as_str())),
+            None => wif,
+        };
+        Ok(WifKey {
+            path: find.path.clone(),
+            script_type,
+            address: find
+                .descriptor
+                .address(network)
+                .ok()
+                .map(|address| address.to_string()),
+            electrum_key,
+        })
+    }
+
+    /// The key as electrum imports it, e.g. `p2wpkh:L1...`, or the bare wif when electrum has no prefix for the script type.
+    pub fn electrum_key(&self) -> &str {
+        self.electrum_key.as_str()
+    }
+}
+
+impl Debug for WifKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WifKey")
+            .field("path", &self.path)
+            .field("script_type", &self.script_type)
+            .field("address", &self.address)
+            .field("electrum_key", &"<redacted>")
+            .finish()
+    }
+}
+
+pub fn derive_wif_keys(
+    finds: &[PathDescriptorFind],
+    master_xpriv: &Xpriv,
+    network: Network,
+) -> Result<Vec<WifKey>, RetrieverError> {
+    finds
+        .iter()
+        .map(|find| WifKey::from_find(find, master_xpriv, network))
+        .collect()
+}
@@ -1,4 +1,34 @@
+use bitceptron_retriever::error::RetrieverError;
+
+use crate::domain::{
+    dump_integrity::DumpIntegrityProblem,
+    sweep::{SweepProblem, SweepSigningProblem},
+};
+
 #[derive(Debug)]
 pub enum GuiError {
     GuiInputIsInsane,
-}
\ No newline at end of file
+    Retriever(RetrieverError),
+    DumpIntegrityCheckFailed(DumpIntegrityProblem),
+    MasterKeyWiped,
+    Sweep(SweepProblem),
+    Signing(SweepSigningProblem),
+}
+
+impl From<RetrieverError> for GuiError {
+    fn from(value: RetrieverError) -> Self {
+        GuiError::Retriever(value)
+    }
+}
+
+impl From<SweepProblem> for GuiError {
+    fn from(value: SweepProblem) -> Self {
+        GuiError::Sweep(value)
+    }
+}
+
+impl From<SweepSigningProblem> for GuiError {
+    fn from(value: SweepSigningProblem) -> Self {
+        GuiError::Signing(value)
+    }
+}
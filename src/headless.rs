@@ -0,0 +1,202 @@
+use std::{
+    fmt::{Debug, Display},
+    sync::Arc,
+};
+
+use bitceptron_retriever::{
+    client::{client_setting::ClientSetting, BitcoincoreRpcClient},
+    covered_descriptors::CoveredDescriptors,
+    error::RetrieverError,
+    explorer::{explorer_setting::ExplorerSetting, Explorer},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::{
+    cli::{read_secrets_from_fd, CliArgs},
+    domain::{
+        dump_integrity::check_dump_integrity,
+        results_export::{write_export_file, ResultsReport, ResultsSource},
+    },
+    gui_error::GuiError,
+    inputs::{
+        bitcoincore_client::BitcoincoreClientInput, explorer::ExplorerInput,
+        retriever_specific::RetrieverSpecificInput, secret::SecretString,
+    },
+    run_functions::{
+        check_for_dump_in_data_dir_or_create_dump_file, create_final_finds,
+        create_new_dump_in_data_dir, get_details_of_finds_from_bitcoincore,
+        get_details_of_finds_from_dump, populate_uspk_set, search_the_uspk_set,
+    },
+};
+
+/// Why the settings given on the command line or to the api could not be fixed.
+#[derive(Debug)]
+pub enum SettingsProblem {
+    Profile(RetrieverError),
+    InvalidBitcoincoreSettings,
+    InvalidExplorationSettings { is_mnemonic_valid: bool },
+    InvalidRetrieverSettings,
+}
+
+impl Display for SettingsProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsProblem::Profile(e) => write!(f, "The profile could not be loaded: {:?}.", e),
+            SettingsProblem::InvalidBitcoincoreSettings => {
+                write!(f, "The bitcoincore settings are not valid.")
+            }
+            SettingsProblem::InvalidExplorationSettings { is_mnemonic_valid } => write!(
+                f,
+                "The exploration settings are not valid. Mnemonic valid: {}.",
+                is_mnemonic_valid
+            ),
+            SettingsProblem::InvalidRetrieverSettings => {
+                write!(f, "The retriever settings are not valid.")
+            }
+        }
+    }
+}
+
+/// The settings of all three sections, fixed as the gui would fix them.
+#[derive(Clone)]
+pub struct FixedSettings {
+    pub client_setting: ClientSetting,
+    pub explorer_setting: ExplorerSetting,
+    pub network: bitcoin::Network,
+    pub data_dir: String,
+    pub verify_dump_hash: bool,
+    pub selected_descriptors: hashbrown::HashSet<CoveredDescriptors>,
+}
+
+/// Leaves out the explorer setting, which holds the mnemonic and passphrase in plain.
+impl Debug for FixedSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FixedSettings")
+            .field("client_setting", &self.client_setting)
+            .field("network", &self.network)
+            .field("data_dir", &self.data_dir)
+            .field("verify_dump_hash", &self.verify_dump_hash)
+            .field("selected_descriptors", &self.selected_descriptors)
+            .finish_non_exhaustive()
+    }
+}
+
+pub fn fix_settings(
+    args: &CliArgs,
+    mnemonic: SecretString,
+    passphrase: SecretString,
+) -> Result<FixedSettings, SettingsProblem> {
+    let mut bitcoincore_client_input = BitcoincoreClientInput::new();
+    let mut explorer_input = ExplorerInput::new();
+    let mut retriever_specific_input = RetrieverSpecificInput::new();
+    args.prefill_inputs(
+        &mut bitcoincore_client_input,
+        &mut explorer_input,
+        &mut retriever_specific_input,
+    )
+    .map_err(SettingsProblem::Profile)?;
+    explorer_input.set_mnemonic_from_gui_input(mnemonic);
+    explorer_input.set_passphrase_from_gui_input(passphrase);
+    bitcoincore_client_input
+        .gui_to_in_use()
+        .map_err(|_| SettingsProblem::InvalidBitcoincoreSettings)?;
+    explorer_input
+        .gui_to_in_use()
+        .map_err(|_| SettingsProblem::InvalidExplorationSettings {
+            is_mnemonic_valid: explorer_input.is_gui_mnemonic_sane(),
+        })?;
+    retriever_specific_input
+        .gui_to_in_use()
+        .map_err(|_| SettingsProblem::InvalidRetrieverSettings)?;
+    Ok(FixedSettings {
+        client_setting: bitcoincore_client_input.to_client_setting(),
+        explorer_setting: explorer_input.to_explorer_setting(),
+        network: explorer_input.get_in_use_network(),
+        data_dir: retriever_specific_input.get_in_use_data_dir(),
+        verify_dump_hash: retriever_specific_input.get_in_use_verify_dump_hash(),
+        selected_descriptors: retriever_specific_input.get_in_use_selected_descriptors(),
+    })
+}
+
+/// Runs dump, populate, search and details the way the gui does, without a window.
+pub async fn run_headless(args: &CliArgs) -> Result<ResultsReport, GuiError> {
+    let (mnemonic, passphrase) = read_secrets_from_fd(args.mnemonic_fd)?;
+    let FixedSettings {
+        client_setting,
+        explorer_setting,
+        network,
+        data_dir,
+        verify_dump_hash,
+        selected_descriptors,
+    } = fix_settings(args, mnemonic, passphrase).map_err(|problem| {
+        error!("{}", problem);
+        GuiError::GuiInputIsInsane
+    })?;
+    let cancellation_token = CancellationToken::new();
+
+    let client = BitcoincoreRpcClient::new(client_setting.clone()).await?;
+    let dump = if args.new_dump {
+        create_new_dump_in_data_dir(data_dir, client.clone(), client_setting).await?
+    } else {
+        check_for_dump_in_data_dir_or_create_dump_file(data_dir, client.clone(), client_setting)
+            .await?
+    };
+    info!("Using the dump file {}.", dump.path_string());
+
+    let (uspk_set, summary) = populate_uspk_set(
+        dump.path.clone(),
+        verify_dump_hash,
+        cancellation_token.clone(),
+    )
+    .await?;
+    // Searching a truncated or corrupted dump would report a clean run, so it stops here as the gui does.
+    let expected_txoutset_hash = dump
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.comparable_txoutset_hash());
+    if summary.txoutset_hash.is_some() && expected_txoutset_hash.is_none() {
+        warn!("No comparable txoutset hash is recorded for this dump. Only the coin count was verified.");
+    }
+    let dump_integrity_problems = check_dump_integrity(&summary, expected_txoutset_hash);
+    for problem in dump_integrity_problems.iter() {
+        error!("{}", problem);
+    }
+    if let Some(problem) = dump_integrity_problems.into_iter().next() {
+        return Err(GuiError::DumpIntegrityCheckFailed(problem));
+    }
+    let explorer = Arc::new(Explorer::new(explorer_setting)?);
+    let finds = search_the_uspk_set(
+        selected_descriptors,
+        Arc::new(uspk_set),
+        explorer,
+        cancellation_token,
+    )
+    .await;
+    info!("{} finds in the explored paths.", finds.len());
+
+    let source = ResultsSource::new(network, Some(&dump));
+    if finds.is_empty() {
+        return Ok(ResultsReport::from_finds(&finds, network, source));
+    }
+    let detailed_finds = if args.details_from_dump {
+        get_details_of_finds_from_dump(finds, dump.path.clone()).await?
+    } else {
+        get_details_of_finds_from_bitcoincore(finds, client).await?
+    };
+    let final_finds = create_final_finds(detailed_finds, network)?;
+    Ok(ResultsReport::from_final_finds(&final_finds, source))
+}
+
+/// Prints the report as json to stdout, or writes it to `--output`.
+pub fn output_headless_report(args: &CliArgs, report: &ResultsReport) -> Result<(), GuiError> {
+    let json = report.to_json()?;
+    match &args.output {
+        Some(output_path) => {
+            write_export_file(output_path, &json)?;
+            info!("Results written to {}.", output_path.display());
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
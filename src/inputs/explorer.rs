@@ -8,7 +8,7 @@ use bitcoin::bip32::DerivationPath;
 
 use crate::gui_error::GuiError;
 
-use super::gui_input::GuiInput;
+use super::{gui_input::GuiInput, secret::SecretString};
 
 #[derive(Debug, Default)]
 pub struct ExplorerInput {
@@ -35,8 +35,8 @@ impl ExplorerInput {
                 in_use_sweep: self.get_gui_sweep(),
                 in_use_exploration_depth: self.get_gui_exploration_depth().parse::<u32>().unwrap(),
                 in_use_network: self.get_gui_network(),
-                in_use_mnemonic: self.get_gui_mnemonic(),
-                in_use_passphrase: self.get_gui_passphrase(),
+                in_use_mnemonic: self.gui_input.gui_mnemonic.mnemonic.clone(),
+                in_use_passphrase: self.gui_input.gui_passphrase.passphrase.clone(),
             },
             false => return Err(GuiError::GuiInputIsInsane),
         };
@@ -49,8 +49,8 @@ impl ExplorerInput {
             panic!("Explorer setting output was called before fixing gui settings")
         }
         ExplorerSetting::new(
-            self.get_in_use_mnemonic(),
-            self.get_in_use_passphrase(),
+            self.get_in_use_mnemonic().to_string(),
+            self.get_in_use_passphrase().to_string(),
             self.get_in_use_base_derivation_paths(),
             self.get_in_use_exploration_path(),
             self.get_in_use_exploration_depth(),
@@ -59,6 +59,13 @@ impl ExplorerInput {
         )
     }
 
+    /// Clears the typed and the in use mnemonic and passphrase, which unfixes the exploration settings.
+    pub fn wipe_secrets(&mut self) {
+        self.gui_input.gui_mnemonic = MnemonicGuiData::new(SecretString::default());
+        self.gui_input.gui_passphrase = PassphraseGuiData::new(SecretString::default());
+        self.in_use = None;
+    }
+
     pub fn set_base_derivation_paths_from_gui_input(&mut self, base_derivation_paths: String) {
         self.gui_input.gui_base_derivation_paths =
             BaseDerivationPathsGuiData::new(base_derivation_paths)
@@ -87,11 +94,11 @@ impl ExplorerInput {
         self.gui_input.gui_network = NetworkGuiData::new(network)
     }
 
-    pub fn set_mnemonic_from_gui_input(&mut self, mnemonic: String) {
+    pub fn set_mnemonic_from_gui_input(&mut self, mnemonic: SecretString) {
         self.gui_input.gui_mnemonic = MnemonicGuiData::new(mnemonic)
     }
 
-    pub fn set_passphrase_from_gui_input(&mut self, passphrase: String) {
+    pub fn set_passphrase_from_gui_input(&mut self, passphrase: SecretString) {
         self.gui_input.gui_passphrase = PassphraseGuiData::new(passphrase)
     }
 
@@ -119,11 +126,11 @@ impl ExplorerInput {
         self.gui_input.gui_network.get_value()
     }
 
-    pub fn get_gui_mnemonic(&self) -> String {
+    pub fn get_gui_mnemonic(&self) -> &str {
         self.gui_input.gui_mnemonic.get_value()
     }
 
-    pub fn get_gui_passphrase(&self) -> String {
+    pub fn get_gui_passphrase(&self) -> &str {
         self.gui_input.gui_passphrase.get_value()
     }
 
@@ -169,17 +176,17 @@ impl ExplorerInput {
         }
     }
 
-    pub fn get_in_use_mnemonic(&self) -> String {
+    pub fn get_in_use_mnemonic(&self) -> &str {
         match &self.in_use {
-            Some(in_use) => in_use.in_use_mnemonic.to_string(),
-            None => "".to_string(),
+            Some(in_use) => in_use.in_use_mnemonic.expose_secret(),
+            None => "",
         }
     }
 
-    pub fn get_in_use_passphrase(&self) -> String {
+    pub fn get_in_use_passphrase(&self) -> &str {
         match &self.in_use {
-            Some(in_use) => in_use.in_use_passphrase.to_string(),
-            None => "".to_string(),
+            Some(in_use) => in_use.in_use_passphrase.expose_secret(),
+            None => "",
         }
     }
 
@@ -284,8 +291,8 @@ impl Default for ExplorerSettingFromGui {
                 DEFAULT_EXPLORATION_DEPTH.to_string(),
             ),
             gui_network: NetworkGuiData::new(bitcoin::Network::Bitcoin),
-            gui_mnemonic: MnemonicGuiData::new("".to_string()),
-            gui_passphrase: PassphraseGuiData::new("".to_string()),
+            gui_mnemonic: MnemonicGuiData::new(SecretString::default()),
+            gui_passphrase: PassphraseGuiData::new(SecretString::default()),
         }
     }
 }
@@ -388,14 +395,14 @@ impl NetworkGuiData {
 
 #[derive(Debug)]
 pub struct MnemonicGuiData {
-    mnemonic: String,
+    mnemonic: SecretString,
     sanity: bool,
 }
 
 impl MnemonicGuiData {
-    fn new(mnemonic: String) -> Self {
-        let mnemonic = mnemonic.trim().to_string();
-        let sanity = bip39::Mnemonic::from_str(mnemonic.as_str()).is_ok();
+    fn new(mnemonic: SecretString) -> Self {
+        let mnemonic = SecretString::new(mnemonic.expose_secret().trim().to_string());
+        let sanity = bip39::Mnemonic::from_str(mnemonic.expose_secret()).is_ok();
         MnemonicGuiData { mnemonic, sanity }
     }
 
@@ -403,21 +410,21 @@ impl MnemonicGuiData {
         self.sanity
     }
 
-    fn get_value(&self) -> String {
-        self.mnemonic.clone()
+    fn get_value(&self) -> &str {
+        self.mnemonic.expose_secret()
     }
 }
 
 #[derive(Debug)]
 pub struct PassphraseGuiData {
-    passphrase: String,
+    passphrase: SecretString,
     sanity: bool,
 }
 
-impl GuiInput for PassphraseGuiData {
-    fn new(value: String) -> Self {
+impl PassphraseGuiData {
+    fn new(passphrase: SecretString) -> Self {
         PassphraseGuiData {
-            passphrase: value,
+            passphrase,
             sanity: true,
         }
     }
@@ -426,8 +433,8 @@ impl GuiInput for PassphraseGuiData {
         self.sanity
     }
 
-    fn get_value(&self) -> String {
-        self.passphrase.to_owned()
+    fn get_value(&self) -> &str {
+        self.passphrase.expose_secret()
     }
 }
 
@@ -439,6 +446,6 @@ pub struct ExplorerSettingInUse {
     in_use_sweep: bool,
     in_use_exploration_depth: u32,
     in_use_network: bitcoin::Network,
-    in_use_mnemonic: String,
-    in_use_passphrase: String,
+    in_use_mnemonic: SecretString,
+    in_use_passphrase: SecretString,
 }
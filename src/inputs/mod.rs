@@ -1,4 +1,5 @@
 pub mod bitcoincore_client;
 pub mod explorer;
 pub mod retriever_specific;
+pub mod secret;
 pub mod gui_input;
\ No newline at end of file
@@ -2,7 +2,7 @@ use std::{path::PathBuf, str::FromStr};
 
 use bitceptron_retriever::covered_descriptors::CoveredDescriptors;
 
-use crate::gui_error::GuiError;
+use crate::{domain::dump_metadata::DEFAULT_STALE_DUMP_THRESHOLD_IN_BLOCKS, gui_error::GuiError};
 
 use super::gui_input::GuiInput;
 
@@ -43,6 +43,11 @@ impl RetrieverSpecificInput {
                     res
                 },
                 in_use_data_dir: self.get_gui_data_dir(),
+                in_use_verify_dump_hash: self.get_gui_verify_dump_hash(),
+                in_use_stale_dump_threshold: self
+                    .get_gui_stale_dump_threshold()
+                    .parse::<u64>()
+                    .unwrap(),
             },
             false => return Err(GuiError::GuiInputIsInsane),
         };
@@ -70,10 +75,18 @@ impl RetrieverSpecificInput {
         self.gui_input.gui_p2tr = p2tr_inclusion
     }
 
+    pub fn set_verify_dump_hash_from_gui_input(&mut self, verify_dump_hash: bool) {
+        self.gui_input.gui_verify_dump_hash = verify_dump_hash
+    }
+
     pub fn set_data_dir_from_gui_input(&mut self, data_dir: String) {
         self.gui_input.gui_data_dir = DataDirGuiData::new(data_dir)
     }
 
+    pub fn set_stale_dump_threshold_from_gui_input(&mut self, stale_dump_threshold: String) {
+        self.gui_input.gui_stale_dump_threshold = StaleDumpThresholdGuiData::new(stale_dump_threshold)
+    }
+
     pub fn get_gui_p2pk(&self) -> bool {
         self.gui_input.gui_p2pk
     }
@@ -94,10 +107,18 @@ impl RetrieverSpecificInput {
         self.gui_input.gui_p2tr
     }
 
+    pub fn get_gui_verify_dump_hash(&self) -> bool {
+        self.gui_input.gui_verify_dump_hash
+    }
+
     pub fn get_gui_data_dir(&self) -> String {
         self.gui_input.gui_data_dir.get_value()
     }
 
+    pub fn get_gui_stale_dump_threshold(&self) -> String {
+        self.gui_input.gui_stale_dump_threshold.get_value()
+    }
+
     pub fn get_in_use_p2pk(&self) -> bool {
         match &self.in_use {
             Some(in_use) => in_use
@@ -150,6 +171,13 @@ impl RetrieverSpecificInput {
         }
     }
 
+    pub fn get_in_use_verify_dump_hash(&self) -> bool {
+        match &self.in_use {
+            Some(in_use) => in_use.in_use_verify_dump_hash,
+            None => false,
+        }
+    }
+
     pub fn get_in_use_data_dir(&self) -> String {
         match &self.in_use {
             Some(in_use) => in_use.in_use_data_dir.to_string(),
@@ -157,6 +185,13 @@ impl RetrieverSpecificInput {
         }
     }
 
+    pub fn get_in_use_stale_dump_threshold(&self) -> u64 {
+        match &self.in_use {
+            Some(in_use) => in_use.in_use_stale_dump_threshold,
+            None => DEFAULT_STALE_DUMP_THRESHOLD_IN_BLOCKS,
+        }
+    }
+
     pub fn is_gui_selected_descriptors_sane(&self) -> bool {
         self.get_gui_p2pk()
             || self.get_gui_p2pkh()
@@ -169,8 +204,14 @@ impl RetrieverSpecificInput {
         self.gui_input.gui_data_dir.is_sane()
     }
 
+    pub fn is_gui_stale_dump_threshold_sane(&self) -> bool {
+        self.gui_input.gui_stale_dump_threshold.is_sane()
+    }
+
     pub fn is_gui_input_sane(&self) -> bool {
-        self.is_gui_data_dir_sane() && self.is_gui_selected_descriptors_sane()
+        self.is_gui_data_dir_sane()
+            && self.is_gui_selected_descriptors_sane()
+            && self.is_gui_stale_dump_threshold_sane()
     }
 
     pub fn is_data_dir_fixed(&self) -> bool {
@@ -186,8 +227,21 @@ impl RetrieverSpecificInput {
             && self.get_gui_p2tr() == self.get_in_use_p2tr()
     }
 
+    pub fn is_stale_dump_threshold_fixed(&self) -> bool {
+        self.in_use.is_some()
+            && (self.get_gui_stale_dump_threshold()
+                == self.get_in_use_stale_dump_threshold().to_string())
+    }
+
+    pub fn is_verify_dump_hash_fixed(&self) -> bool {
+        self.in_use.is_some() && (self.get_gui_verify_dump_hash() == self.get_in_use_verify_dump_hash())
+    }
+
     pub fn is_input_fixed(&self) -> bool {
-        self.is_data_dir_fixed() && self.is_selected_descriptors_fixed()
+        self.is_data_dir_fixed()
+            && self.is_verify_dump_hash_fixed()
+            && self.is_selected_descriptors_fixed()
+            && self.is_stale_dump_threshold_fixed()
     }
 }
 
@@ -199,6 +253,8 @@ pub struct RetrieverSpecificSettingFromGui {
     gui_p2shwpkh: bool,
     gui_p2tr: bool,
     gui_data_dir: DataDirGuiData,
+    gui_verify_dump_hash: bool,
+    gui_stale_dump_threshold: StaleDumpThresholdGuiData,
 }
 
 impl Default for RetrieverSpecificSettingFromGui {
@@ -210,6 +266,10 @@ impl Default for RetrieverSpecificSettingFromGui {
             gui_p2shwpkh: true,
             gui_p2tr: true,
             gui_data_dir: DataDirGuiData::new("".to_string()),
+            gui_verify_dump_hash: false,
+            gui_stale_dump_threshold: StaleDumpThresholdGuiData::new(
+                DEFAULT_STALE_DUMP_THRESHOLD_IN_BLOCKS.to_string(),
+            ),
         }
     }
 }
@@ -239,8 +299,35 @@ impl GuiInput for DataDirGuiData {
     }
 }
 
+#[derive(Debug)]
+pub struct StaleDumpThresholdGuiData {
+    stale_dump_threshold: String,
+    sanity: bool,
+}
+
+impl GuiInput for StaleDumpThresholdGuiData {
+    fn new(value: String) -> Self {
+        let stale_dump_threshold = value.trim().to_string();
+        let sanity = stale_dump_threshold.parse::<u64>().is_ok();
+        StaleDumpThresholdGuiData {
+            stale_dump_threshold,
+            sanity,
+        }
+    }
+
+    fn is_sane(&self) -> bool {
+        self.sanity
+    }
+
+    fn get_value(&self) -> String {
+        self.stale_dump_threshold.to_owned()
+    }
+}
+
 #[derive(Debug)]
 pub struct RetrieverSpecificSettingInUse {
     in_use_selected_descriptors: hashbrown::HashSet<CoveredDescriptors>,
     in_use_data_dir: String,
+    in_use_verify_dump_hash: bool,
+    in_use_stale_dump_threshold: u64,
 }
@@ -0,0 +1,146 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Deserializer};
+use zeroize::Zeroizing;
+
+/// A string holding a secret, like the mnemonic or the passphrase. It is wiped from memory when dropped and
+/// shows up as `<redacted>` in debug output, so it can not leak through logs.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    pub fn new(secret: String) -> Self {
+        SecretString(Zeroizing::new(secret))
+    }
+
+    pub fn expose_secret(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString::new(secret)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(SecretString::new)
+    }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use bitceptron_retriever::client::client_setting::ClientSetting;
+    use bitcoin::Network;
+    use tracing::error;
+    use zeroize::Zeroizing;
+
+    use crate::{
+        api::http::HttpRequest,
+        app_message::{setting_input_in_gui::SettingInputInGuiMessage, AppMessage},
+        headless::FixedSettings,
+        inputs::explorer::ExplorerInput,
+        RetrieverApp,
+    };
+
+    use super::SecretString;
+
+    const MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const PASSPHRASE: &str = "correct horse battery staple";
+
+    #[derive(Clone, Default)]
+    struct LogBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn mnemonic_and_passphrase_never_reach_the_logs() {
+        let logs = LogBuffer::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let mut explorer_input = ExplorerInput::new();
+            explorer_input.set_mnemonic_from_gui_input(SecretString::new(MNEMONIC.to_string()));
+            explorer_input.set_passphrase_from_gui_input(SecretString::new(PASSPHRASE.to_string()));
+            explorer_input.gui_to_in_use().unwrap();
+            assert_eq!(explorer_input.get_in_use_mnemonic(), MNEMONIC);
+            error!("{:?}", explorer_input);
+
+            explorer_input.set_exploration_depth_from_gui_input("0".to_string());
+            let e = explorer_input.gui_to_in_use().unwrap_err();
+            error!("{:?}", e);
+
+            let message = AppMessage::SettingInputInGuiChanged(
+                SettingInputInGuiMessage::MnemonicChanged(SecretString::new(MNEMONIC.to_string())),
+            );
+            error!("{:?}", message);
+
+            explorer_input.set_exploration_depth_from_gui_input("1".to_string());
+            explorer_input.gui_to_in_use().unwrap();
+            let mut app = RetrieverApp::default();
+            app.explorer_setting_input = explorer_input;
+            app.explorer = Arc::new(app.create_explorer().unwrap());
+            error!("{:?}", app);
+
+            let settings = FixedSettings {
+                client_setting: ClientSetting::default(),
+                explorer_setting: app.explorer_setting_input.to_explorer_setting(),
+                network: Network::Bitcoin,
+                data_dir: String::new(),
+                verify_dump_hash: false,
+                selected_descriptors: Default::default(),
+            };
+            error!("{:?}", settings);
+
+            let request = HttpRequest {
+                method: "POST".to_string(),
+                path: "/settings".to_string(),
+                headers: vec![(
+                    "authorization".to_string(),
+                    format!("Bearer {}", PASSPHRASE),
+                )],
+                body: Zeroizing::new(
+                    format!(
+                        "{{\"mnemonic\": \"{}\", \"passphrase\": \"{}\"}}",
+                        MNEMONIC, PASSPHRASE
+                    )
+                    .into_bytes(),
+                ),
+            };
+            error!("{:?}", request);
+        });
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            logs.lines().filter(|line| line.contains("ERROR")).count(),
+            6
+        );
+        assert!(logs.contains("<redacted>"));
+        assert!(!logs.contains("abandon"));
+        assert!(!logs.contains(PASSPHRASE));
+    }
+}
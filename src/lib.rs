@@ -1,70 +1,307 @@
-use std::sync::Arc;
+use std::{path::PathBuf, str::FromStr, sync::Arc, time::{Duration, Instant}};
 
-use app_message::{setting_input_in_gui::SettingInputInGuiMessage, AppMessage};
+use app_message::{setting_input_in_gui::SettingInputInGuiMessage, AppMessage, Operation};
+use cli::CliArgs;
 use bitceptron_retriever::{
     client::{client_setting::ClientSetting, BitcoincoreRpcClient},
     error::RetrieverError,
-    explorer::{explorer_setting::ExplorerSetting, Explorer},
-    path_pairs::{PathDescriptorPair, PathScanResultDescriptorTrio},
+    explorer::Explorer,
 };
+use bitcoin::{bip32::Xpriv, Address};
 use iced::{
     executor,
-    widget:: Column,
-    Application, Command,
+    widget::{text_input, Column},
+    Application, Command, Subscription,
 };
+use gui_error::GuiError;
+use itertools::Itertools;
 use inputs::{
     bitcoincore_client::BitcoincoreClientInput, explorer::ExplorerInput,
-    retriever_specific::RetrieverSpecificInput,
+    retriever_specific::RetrieverSpecificInput, secret::SecretString,
+};
+use domain::{
+    broadcast::{BroadcastStatus, MempoolAcceptance, SweepBroadcast, CONFIRMATION_CHECK_INTERVAL},
+    fee_estimates::FeeEstimates,
+    core_wallet::{CoreWalletImportReport, CoreWalletRequest},
+    descriptor_export::{ImportDescriptorsVariant, SpendableDescriptorsDestination},
+    dump_catalog::DumpFileEntry,
+    dump_integrity::{check_dump_integrity, DumpIntegrityProblem},
+    dump_preflight::{DumpIntent, PendingDump},
+    finds::{DetailedFind, PathDescriptorFind},
+    mnemonic_entry::MnemonicWordGrid,
+    profiles::{is_valid_profile_name, SettingsProfile},
+    message_signing::{sign_message, verify_message, MessageSignatureFormat, MessageSigningProblem, SignedMessage},
+    results_export::{ResultsReport, ResultsSource},
+    results_table::ResultsTable,
+    snapshot_header::InspectedSnapshot,
+    split_workflow::{OfflineDerivationSummary, OnlineMatchReport},
+    sweep::{PreparedSweep, SignedSweep, SweepInput, SweepPlan, SweepProblem},
+    wif_export::{derive_wif_keys, WifKey, WIF_EXPORT_CONFIRMATION_PHRASE},
 };
 use run_functions::{
-    check_for_dump_in_data_dir_or_create_dump_file, create_final_finds, create_new_dump_in_data_dir, get_details_of_finds_from_bitcoincore, populate_uspk_set, search_the_uspk_set
+    check_for_dump_in_data_dir_or_create_dump_file, create_final_finds, create_new_dump_in_data_dir, delete_dump_file_from_data_dir, describe_dump_file, send_finds_to_core_wallet, export_import_descriptors, export_results, find_latest_dump_for_node, get_details_of_finds_from_bitcoincore, get_details_of_finds_from_dump, get_node_tip_height, inspect_snapshot_file, derive_scripts_offline, match_derived_scripts_online, get_fee_estimates, prepare_sweep_psbt, sign_sweep, test_sweep_mempool_acceptance, broadcast_sweep, get_sweep_broadcast_status, list_dump_files_in_data_dir, list_settings_profiles, load_settings_profile, save_settings_profile, populate_uspk_set, run_dump_preflight, search_the_uspk_set
 };
 use tokio_util::sync::CancellationToken;
-use tracing::error;
+use tracing::{error, warn};
 use uspk_set::UnspentScriptPubKeysSet;
 use view_elements::{
-    bitcoincore_client_setting_row, exploration_setting_row, final_finds::FinalFinds, results_row::results_row, retriever_setting_row, run_row::run_row
+    bitcoincore_client_setting_row, exploration_setting_row, final_finds::FinalFinds, mnemonic_word_grid::mnemonic_word_input_id, profiles_row::profiles_row, results_row::results_row, retriever_setting_row, run_row::run_row
 };
+use zeroize::{Zeroize, Zeroizing};
 
+pub mod api;
 pub mod app_message;
 pub mod app_status;
+pub mod cli;
 pub mod domain;
 pub mod gui_error;
+pub mod headless;
 pub mod inputs;
+pub mod node_client;
 pub mod retriever_styles;
 pub mod run_functions;
 pub mod status;
 pub mod view_elements;
 pub mod uspk_set;
 
+/// How often the idle time is checked against the auto-wipe setting.
+const IDLE_WIPE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Default)]
 pub struct RetrieverApp {
     // Inputs
     bitcoincore_client_setting_input: BitcoincoreClientInput,
     explorer_setting_input: ExplorerInput,
     retriever_specific_setting_input: RetrieverSpecificInput,
+    // Profiles
+    profile_names: Vec<String>,
+    selected_profile: Option<String>,
+    profile_name_input: String,
+    loaded_profile: Option<String>,
+    last_saved_profile: Option<PathBuf>,
     // Settings
     client_setting: ClientSetting,
-    explorer_setting: ExplorerSetting,
     // Errors
-    errors: Vec<Arc<RetrieverError>>,
+    errors: Vec<Arc<GuiError>>,
     // Explorer
     explorer: Arc<Explorer>,
+    is_master_key_loaded: bool,
+    // Secrets
+    is_mnemonic_shown: bool,
+    is_passphrase_shown: bool,
+    mnemonic_word_grid: Option<MnemonicWordGrid>,
+    wipe_secrets_after_search: bool,
+    wipe_secrets_after_idle_input: String,
+    last_activity: Option<Instant>,
     // DB
     uspk_set: Arc<UnspentScriptPubKeysSet>,
+    // Dump preflight
+    pending_dump: Option<PendingDump>,
+    // Dump files
+    dump_files: Vec<DumpFileEntry>,
+    selected_dump: Option<DumpFileEntry>,
+    pending_dump_deletion: Option<DumpFileEntry>,
+    snapshot_import_path: String,
+    inspected_snapshot: Option<InspectedSnapshot>,
+    node_tip_height: Option<u64>,
+    dump_integrity_problems: Vec<DumpIntegrityProblem>,
     // Finds
-    finds: Vec<PathDescriptorPair>,
-    detailed_finds: Option<Vec<PathScanResultDescriptorTrio>>,
+    finds: Vec<PathDescriptorFind>,
+    detailed_finds: Option<Vec<DetailedFind>>,
     final_finds: Vec<FinalFinds>,
+    expanded_finds: hashbrown::HashSet<u64>,
+    shown_qr_codes: hashbrown::HashSet<u64>,
+    results_table: ResultsTable,
+    last_export: Option<PathBuf>,
+    pending_spendable_descriptors: Option<SpendableDescriptorsDestination>,
+    // Air-gapped split workflow
+    offline_derivation_summary: Option<OfflineDerivationSummary>,
+    derived_scripts_file_input: String,
+    online_match_report: Option<OnlineMatchReport>,
+    is_online_match_in_progress: bool,
+    // Message signing
+    message_signing_result_num: Option<u64>,
+    message_to_sign_input: String,
+    signed_message: Option<Result<SignedMessage, MessageSigningProblem>>,
+    verify_address_input: String,
+    verify_message_input: String,
+    verify_signature_input: String,
+    message_verification: Option<Result<MessageSignatureFormat, MessageSigningProblem>>,
+    // Wif keys
+    is_wif_keys_pending: bool,
+    wif_keys_confirmation_input: String,
+    wif_keys: Vec<WifKey>,
+    // Core wallet
+    core_wallet_name: String,
+    core_wallet_spendable: bool,
+    is_core_wallet_import_in_progress: bool,
+    core_wallet_import_report: Option<CoreWalletImportReport>,
+    // Sweep
+    sweep_destination_input: String,
+    sweep_fee_rate_input: String,
+    fee_estimates: Option<FeeEstimates>,
+    is_getting_fee_estimates_in_progress: bool,
+    prepared_sweep: Option<PreparedSweep>,
+    signed_sweep: Option<SignedSweep>,
+    is_sweep_in_progress: bool,
+    sweep_mempool_acceptance: Option<MempoolAcceptance>,
+    is_sweep_broadcast_pending: bool,
+    sweep_broadcast: Option<SweepBroadcast>,
+    is_sweep_broadcast_status_check_in_progress: bool,
     // State control
     is_dump_file_ready: bool,
     is_populating_in_progress: bool,
     is_search_in_progress: bool,
+    is_getting_details_in_progress: bool,
     // Cancellation tokens,
     populating_cancellation_token: CancellationToken,
     search_cancellation_token: CancellationToken,
 }
 
+impl RetrieverApp {
+    fn dump_preflight_command(&self, intent: DumpIntent, client: BitcoincoreRpcClient) -> Command<AppMessage> {
+        let client_setting = self.client_setting.clone();
+        let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir().clone();
+        Command::perform(run_dump_preflight(client_setting, data_dir), move |preflight_result| {
+            match preflight_result {
+                Ok(report) => AppMessage::DumpPreflightReady(intent, client, report),
+                Err(e) => AppMessage::Error(Arc::new(e.into())),
+            }
+        })
+    }
+
+    fn refresh_dump_files_command(&self) -> Command<AppMessage> {
+        let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir().clone();
+        Command::perform(list_dump_files_in_data_dir(data_dir), AppMessage::DumpFilesListed)
+    }
+
+    fn dump_command(&self, intent: DumpIntent, client: BitcoincoreRpcClient) -> Command<AppMessage> {
+        let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir().clone();
+        let client_setting = self.client_setting.clone();
+        let on_dump_result = |dump_result: Result<DumpFileEntry, RetrieverError>| match dump_result {
+            Ok(entry) => AppMessage::DumpFilePrepared(entry),
+            Err(e) => AppMessage::Error(Arc::new(e.into())),
+        };
+        match intent {
+            DumpIntent::CreateNew => Command::perform(create_new_dump_in_data_dir(data_dir, client, client_setting), on_dump_result),
+            DumpIntent::UseOrCreate => Command::perform(check_for_dump_in_data_dir_or_create_dump_file(data_dir, client, client_setting), on_dump_result),
+        }
+    }
+
+    /// Pairs every find with the height of its earliest unspent, when details are known.
+    fn finds_with_earliest_height(&self) -> Vec<(PathDescriptorFind, Option<u64>)> {
+        self.finds
+            .iter()
+            .map(|find| {
+                let earliest_height = self.detailed_finds.iter().flatten()
+                    .find(|detailed| detailed.path == find.path && detailed.descriptor == find.descriptor)
+                    .and_then(|detailed| detailed.unspents.iter().map(|utxo| utxo.height).min());
+                (find.clone(), earliest_height)
+            })
+            .collect()
+    }
+
+    /// The sweep of every found unspent, as currently typed in the sweep panel.
+    fn sweep_plan(&self) -> Result<SweepPlan, SweepProblem> {
+        self.sweep_plan_at(&self.sweep_fee_rate_input)
+    }
+
+    fn sweep_plan_at(&self, fee_rate_input: &str) -> Result<SweepPlan, SweepProblem> {
+        let inputs = SweepInput::from_detailed_finds(self.detailed_finds.as_deref().unwrap_or_default());
+        SweepPlan::new(inputs, &self.sweep_destination_input, fee_rate_input, self.explorer_setting_input.get_in_use_network())
+    }
+
+    /// The master key of the last search, or an error once the secrets were wiped.
+    fn master_xpriv(&mut self) -> Option<Xpriv> {
+        if !self.is_master_key_loaded {
+            self.errors.push(Arc::new(GuiError::MasterKeyWiped));
+            return None;
+        }
+        Some(*self.explorer.get_master_xpriv().as_ref())
+    }
+
+    /// Minutes of inactivity after which the secrets are wiped, if set.
+    fn wipe_secrets_after_idle(&self) -> Option<Duration> {
+        self.wipe_secrets_after_idle_input
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| Duration::from_secs(minutes * 60))
+    }
+
+    fn has_secrets(&self) -> bool {
+        self.is_master_key_loaded
+            || !self.explorer_setting_input.get_gui_mnemonic().is_empty()
+            || !self.explorer_setting_input.get_gui_passphrase().is_empty()
+            || !self.wif_keys.is_empty()
+    }
+
+    /// The mnemonic and passphrase are only copied out of their inputs here, so the app state never holds them in plain.
+    fn create_explorer(&self) -> Result<Explorer, GuiError> {
+        if !self.explorer_setting_input.is_input_fixed() {
+            return Err(GuiError::GuiInputIsInsane);
+        }
+        Ok(Explorer::new(self.explorer_setting_input.to_explorer_setting())?)
+    }
+
+    /// Clears the mnemonic and passphrase inputs, the in use settings, the master key and the wif keys. Finds stay.
+    fn wipe_secrets(&mut self) {
+        if self.is_search_in_progress {
+            self.search_cancellation_token.cancel();
+            self.search_cancellation_token = CancellationToken::new();
+            self.is_search_in_progress = false;
+        }
+        self.explorer_setting_input.wipe_secrets();
+        if let Some(mnemonic_word_grid) = &mut self.mnemonic_word_grid {
+            mnemonic_word_grid.clear();
+        }
+        if let Some(explorer) = Arc::get_mut(&mut self.explorer) {
+            explorer.zeroize();
+        }
+        self.explorer = Default::default();
+        self.is_master_key_loaded = false;
+        // Dropping the keys zeroizes them.
+        self.wif_keys.clear();
+        self.is_wif_keys_pending = false;
+        self.wif_keys_confirmation_input.clear();
+        self.pending_spendable_descriptors = None;
+        self.is_mnemonic_shown = false;
+        self.is_passphrase_shown = false;
+    }
+
+    fn import_descriptors_export_command(&mut self, variant: ImportDescriptorsVariant) -> Command<AppMessage> {
+        let finds_with_earliest_height = self.finds_with_earliest_height();
+        let Some(master_xpriv) = self.master_xpriv() else {
+            return Command::none();
+        };
+        let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir();
+        let client_setting = self.bitcoincore_client_setting_input.is_input_fixed().then(|| self.client_setting.clone());
+        Command::perform(export_import_descriptors(finds_with_earliest_height, master_xpriv, variant, data_dir, client_setting), |export_result| match export_result {
+            Ok(export_path) => AppMessage::ResultsExported(export_path),
+            Err(e) => AppMessage::Error(Arc::new(e.into())),
+        })
+    }
+
+    fn core_wallet_import_command(&mut self) -> Command<AppMessage> {
+        let request = CoreWalletRequest {
+            wallet_name: self.core_wallet_name.trim().to_string(),
+            watch_only: !self.core_wallet_spendable,
+        };
+        let finds_with_earliest_height = self.finds_with_earliest_height();
+        let Some(master_xpriv) = self.master_xpriv() else {
+            return Command::none();
+        };
+        let client_setting = self.client_setting.clone();
+        self.is_core_wallet_import_in_progress = true;
+        self.core_wallet_import_report = None;
+        Command::perform(send_finds_to_core_wallet(finds_with_earliest_height, master_xpriv, request, client_setting), |import_result| match import_result {
+            Ok(report) => AppMessage::CoreWalletImportFinished(report),
+            Err(e) => AppMessage::OperationFailed(Operation::CoreWalletImport, Arc::new(e.into())),
+        })
+    }
+}
+
 impl Application for RetrieverApp {
     type Executor = executor::Default;
 
@@ -72,11 +309,15 @@ impl Application for RetrieverApp {
 
     type Theme = iced::Theme;
 
-    type Flags = ();
+    type Flags = CliArgs;
 
-    fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        let app = RetrieverApp::default();
-        (app, Command::none())
+    fn new(flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        let mut app = RetrieverApp::default();
+        if let Err(e) = flags.prefill_inputs(&mut app.bitcoincore_client_setting_input, &mut app.explorer_setting_input, &mut app.retriever_specific_setting_input) {
+            app.errors.push(Arc::new(e.into()));
+        }
+        app.loaded_profile = flags.profile;
+        (app, Command::perform(list_settings_profiles(), AppMessage::ProfilesListed))
     }
 
     fn title(&self) -> String {
@@ -84,6 +325,16 @@ impl Application for RetrieverApp {
     }
 
     fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
+        // The timers and the replies of the broadcast status check are not activity, or the idle wipe would never fire.
+        if !matches!(
+            message,
+            AppMessage::CheckIdleWipe
+                | AppMessage::CheckSweepBroadcastStatus
+                | AppMessage::SweepBroadcastStatusChecked(_)
+                | AppMessage::OperationFailed(Operation::SweepBroadcastStatusCheck, _)
+        ) {
+            self.last_activity = Some(Instant::now());
+        }
         match message {
             AppMessage::SettingInputInGuiChanged(input_change) => match input_change {
                 SettingInputInGuiMessage::BitcoincoreUrlChanged(new_url) => {
@@ -100,7 +351,7 @@ impl Application for RetrieverApp {
                     .set_cookie_path_from_gui_input(new_cookie_path),
                 SettingInputInGuiMessage::NetworkChanged(network) => self.explorer_setting_input.set_network_from_gui_input(network),
                 SettingInputInGuiMessage::MnemonicChanged(mnemonic) => {
-                    self.explorer_setting_input.set_mnemonic_from_gui_input(mnemonic.clone());
+                    self.explorer_setting_input.set_mnemonic_from_gui_input(mnemonic);
                 },
                 SettingInputInGuiMessage::BaseDerivationPathsChanged(base_derivation_paths) => self.explorer_setting_input.set_base_derivation_paths_from_gui_input(base_derivation_paths),
                 SettingInputInGuiMessage::BaseDerivationPathsFromPresetsChanged(base_derivation_paths_from_presets) => {
@@ -118,6 +369,8 @@ impl Application for RetrieverApp {
                 SettingInputInGuiMessage::P2shwpkhInclusionChanged(p2shwpkh_inclusion) => self.retriever_specific_setting_input.set_p2shwpkh_inclusion_from_gui_input(p2shwpkh_inclusion),
                 SettingInputInGuiMessage::P2trInclusionChanged(p2tr_inclusion) => self.retriever_specific_setting_input.set_p2tr_inclusion_from_gui_input(p2tr_inclusion),
                 SettingInputInGuiMessage::DataDirChanged(data_dir) => self.retriever_specific_setting_input.set_data_dir_from_gui_input(data_dir),
+                SettingInputInGuiMessage::StaleDumpThresholdChanged(stale_dump_threshold) => self.retriever_specific_setting_input.set_stale_dump_threshold_from_gui_input(stale_dump_threshold),
+                SettingInputInGuiMessage::VerifyDumpHashChanged(verify_dump_hash) => self.retriever_specific_setting_input.set_verify_dump_hash_from_gui_input(verify_dump_hash),
                 SettingInputInGuiMessage::PassphraseChanged(passphrase) => self.explorer_setting_input.set_passphrase_from_gui_input(passphrase),
             },
             AppMessage::SettingInputGotFixed(input_fixed) => match input_fixed {
@@ -127,10 +380,10 @@ impl Application for RetrieverApp {
                 },
                 app_message::setting_input_fixed::SettingInputFixedMessage::ExplorerSettingFixed => {
                     let _ = self.explorer_setting_input.gui_to_in_use();
-                    self.explorer_setting = self.explorer_setting_input.to_explorer_setting();
                 },
                 app_message::setting_input_fixed::SettingInputFixedMessage::RetrieverSettingFixed => {
                     let _ = self.retriever_specific_setting_input.gui_to_in_use();
+                    return self.refresh_dump_files_command();
                 },
             },
             // AppMessage::CreateExplorer => {
@@ -138,7 +391,7 @@ impl Application for RetrieverApp {
             //         Ok(explorer) => {
             //             self.explorer = Arc::new(explorer); 
             //             },
-            //         Err(e) => self.errors.push(Arc::new(e)),
+            //         Err(e) => self.errors.push(Arc::new(e.into())),
             //     }
             // },
             // AppMessage::RetrieverCreated(retriever) => {
@@ -150,50 +403,139 @@ impl Application for RetrieverApp {
                 return Command::perform(BitcoincoreRpcClient::new(client_setting), |client_result| {
                 match client_result {
                     Ok(client) => AppMessage::ClientCreatedForNewFileSoCreateDumpFile(client),
-                    Err(e) => AppMessage::Error(Arc::new(e)),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
                 }
             })},
             AppMessage::ClientCreatedForNewFileSoCreateDumpFile(client) => {
-                let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir().clone();
-                return Command::perform(create_new_dump_in_data_dir(data_dir, client), |dump_result| {
-                    match dump_result {
-                        Ok(_) => AppMessage::DumpFilePrepared,
-                        Err(e) => AppMessage::Error(Arc::new(e)),
-                    }
-                });
+                return self.dump_preflight_command(DumpIntent::CreateNew, client);
             },
             AppMessage::CreateClientForDumpFileAndThenPrepare => {
                 let client_setting = self.client_setting.clone();
                 return Command::perform(BitcoincoreRpcClient::new(client_setting), |client_result| {
                 match client_result {
                     Ok(client) => AppMessage::ClientCreatedForDumpFileSoPrepareDumpFile(client),
-                    Err(e) => AppMessage::Error(Arc::new(e)),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
                 }
             })},
             AppMessage::ClientCreatedForDumpFileSoPrepareDumpFile(client) => {
                 let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir().clone();
-                return Command::perform(check_for_dump_in_data_dir_or_create_dump_file(data_dir, client), |dump_result| {
-                    match dump_result {
-                        Ok(_) => AppMessage::DumpFilePrepared,
-                        Err(e) => AppMessage::Error(Arc::new(e)),
+                let client_setting = self.client_setting.clone();
+                return Command::perform(find_latest_dump_for_node(data_dir, client_setting), move |lookup_result| {
+                    match lookup_result {
+                        Ok(entry) => AppMessage::LatestDumpForNodeLookedUp(client, entry),
+                        Err(e) => AppMessage::Error(Arc::new(e.into())),
                     }
                 });
             },
-            AppMessage::DumpFilePrepared => self.is_dump_file_ready = true,
+            AppMessage::LatestDumpForNodeLookedUp(client, entry) => match entry {
+                Some(entry) => return self.update(AppMessage::DumpFilePrepared(entry)),
+                None => return self.dump_preflight_command(DumpIntent::UseOrCreate, client),
+            },
+            AppMessage::DumpPreflightReady(intent, client, report) => {
+                if report.has_problems() {
+                    self.pending_dump = Some(PendingDump { intent, client, report });
+                } else {
+                    return self.dump_command(intent, client);
+                }
+            },
+            AppMessage::DumpPreflightConfirmed => {
+                if let Some(pending_dump) = self.pending_dump.take() {
+                    return self.dump_command(pending_dump.intent, pending_dump.client);
+                }
+            },
+            AppMessage::DumpPreflightDismissed => self.pending_dump = None,
+            AppMessage::DumpFilePrepared(entry) => {
+                self.is_dump_file_ready = true;
+                self.selected_dump = Some(entry);
+                self.dump_integrity_problems = vec![];
+                let mut commands = vec![self.refresh_dump_files_command()];
+                if self.bitcoincore_client_setting_input.is_input_fixed() {
+                    let client_setting = self.client_setting.clone();
+                    commands.push(Command::perform(get_node_tip_height(client_setting), |tip_result| match tip_result {
+                        Ok(tip_height) => AppMessage::NodeTipHeightReady(tip_height),
+                        Err(e) => AppMessage::Error(Arc::new(e.into())),
+                    }));
+                }
+                return Command::batch(commands);
+            },
+            AppMessage::NodeTipHeightReady(tip_height) => self.node_tip_height = Some(tip_height),
+            AppMessage::RefreshDumpFiles => return self.refresh_dump_files_command(),
+            AppMessage::DumpFilesListed(dump_files) => self.dump_files = dump_files,
+            AppMessage::DumpFileSelected(dump_file_path) => {
+                let client_setting = self.client_setting.clone();
+                return Command::perform(describe_dump_file(dump_file_path, client_setting), |describe_result| match describe_result {
+                    Ok(entry) => AppMessage::DumpFilePrepared(entry),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
+                });
+            },
+            AppMessage::DeleteDumpFileRequested(entry) => self.pending_dump_deletion = Some(entry),
+            AppMessage::DeleteDumpFileConfirmed => {
+                if let Some(entry) = self.pending_dump_deletion.take() {
+                    let dump_file_path = entry.path.clone();
+                    return Command::perform(delete_dump_file_from_data_dir(entry.path), move |delete_result| match delete_result {
+                        Ok(_) => AppMessage::DumpFileDeleted(dump_file_path.clone()),
+                        Err(e) => AppMessage::Error(Arc::new(e.into())),
+                    });
+                }
+            },
+            AppMessage::DeleteDumpFileDismissed => self.pending_dump_deletion = None,
+            AppMessage::SnapshotImportPathChanged(snapshot_import_path) => {
+                self.snapshot_import_path = snapshot_import_path;
+                self.inspected_snapshot = None;
+            },
+            AppMessage::InspectSnapshotFile => {
+                let snapshot_file_path = PathBuf::from(self.snapshot_import_path.trim());
+                return Command::perform(inspect_snapshot_file(snapshot_file_path), |inspect_result| match inspect_result {
+                    Ok(inspected_snapshot) => AppMessage::SnapshotInspected(inspected_snapshot),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
+                });
+            },
+            AppMessage::SnapshotInspected(inspected_snapshot) => self.inspected_snapshot = Some(inspected_snapshot),
+            AppMessage::UseInspectedSnapshot => {
+                if let Some(inspected_snapshot) = self.inspected_snapshot.take() {
+                    return self.update(AppMessage::DumpFileSelected(inspected_snapshot.path));
+                }
+            },
+            AppMessage::DismissInspectedSnapshot => self.inspected_snapshot = None,
+            AppMessage::DumpFileDeleted(dump_file_path) => {
+                if self.selected_dump.as_ref().is_some_and(|selected| selected.path == dump_file_path) {
+                    self.selected_dump = None;
+                    self.is_dump_file_ready = false;
+                }
+                return self.refresh_dump_files_command();
+            },
             AppMessage::PopulateUtxoDB => {
-                let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir().clone();
+                let Some(selected_dump) = &self.selected_dump else {
+                    self.errors.push(Arc::new(RetrieverError::NoDumpFileInDataDir.into()));
+                    return Command::none();
+                };
+                let dump_file_path = selected_dump.path.clone();
+                let verify_dump_hash = self.retriever_specific_setting_input.get_in_use_verify_dump_hash();
                 let cancellation_token = self.populating_cancellation_token.clone();
                 self.is_populating_in_progress = true;
-                return Command::perform(populate_uspk_set(data_dir, cancellation_token), |populate_result| match populate_result {
-                    Ok(set) => AppMessage::SetPopulated(set),
+                self.dump_integrity_problems = vec![];
+                return Command::perform(populate_uspk_set(dump_file_path, verify_dump_hash, cancellation_token), |populate_result| match populate_result {
+                    Ok((set, summary)) => AppMessage::SetPopulated(set, summary),
                     Err(e) => {
-                        AppMessage::Error(Arc::new(e))
+                        AppMessage::OperationFailed(Operation::Populating, Arc::new(e.into()))
                     },
                 });
             },
-            AppMessage::SetPopulated(set) => {
+            AppMessage::SetPopulated(set, summary) => {
                 self.uspk_set = Arc::new(set);
                 self.is_populating_in_progress = false;
+                let expected_txoutset_hash = self
+                    .selected_dump
+                    .as_ref()
+                    .and_then(|dump| dump.metadata.as_ref())
+                    .and_then(|metadata| metadata.comparable_txoutset_hash());
+                if summary.txoutset_hash.is_some() && expected_txoutset_hash.is_none() {
+                    warn!("No comparable txoutset hash is recorded for this dump. Only the coin count was verified.");
+                }
+                self.dump_integrity_problems = check_dump_integrity(&summary, expected_txoutset_hash);
+                for problem in self.dump_integrity_problems.iter() {
+                    self.errors.push(Arc::new(GuiError::DumpIntegrityCheckFailed(problem.clone())));
+                }
             },
             AppMessage::Search => {
                 let select_descriptors = self.retriever_specific_setting_input.get_in_use_selected_descriptors().clone();
@@ -202,10 +544,13 @@ impl Application for RetrieverApp {
                 self.finds = Default::default();
                 self.detailed_finds = Default::default();
                 self.final_finds = Default::default();
+                self.expanded_finds = Default::default();
+                self.shown_qr_codes = Default::default();
 
-                match Explorer::new(self.explorer_setting.clone()) {
+                match self.create_explorer() {
                     Ok(explorer) => {
                         self.explorer = Arc::new(explorer); 
+                        self.is_master_key_loaded = true;
                         },
                     Err(e) => self.errors.push(Arc::new(e)),
                 }
@@ -218,41 +563,434 @@ impl Application for RetrieverApp {
                     cancellation_token,
                     ), AppMessage::SearchResultPrepared);
             },
+            AppMessage::DeriveScriptsOffline => {
+                let select_descriptors = self.retriever_specific_setting_input.get_in_use_selected_descriptors().clone();
+                let cancellation_token = self.search_cancellation_token.clone();
+                match self.create_explorer() {
+                    Ok(explorer) => {
+                        self.explorer = Arc::new(explorer);
+                        self.is_master_key_loaded = true;
+                        },
+                    Err(e) => {
+                        self.errors.push(Arc::new(e));
+                        return Command::none();
+                    },
+                }
+                let explorer = self.explorer.clone();
+                let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir();
+                let network = self.explorer_setting_input.get_in_use_network();
+                self.offline_derivation_summary = None;
+                self.is_search_in_progress = true;
+                return Command::perform(derive_scripts_offline(select_descriptors, explorer, data_dir, network, cancellation_token), |derivation_result| match derivation_result {
+                    Ok(summary) => AppMessage::ScriptsDerivedOffline(summary),
+                    Err(e) => AppMessage::OperationFailed(Operation::Search, Arc::new(e.into())),
+                });
+            },
+            AppMessage::ScriptsDerivedOffline(summary) => {
+                self.is_search_in_progress = false;
+                self.offline_derivation_summary = Some(summary);
+            },
+            AppMessage::DerivedScriptsFilePathChanged(file_path) => self.derived_scripts_file_input = file_path,
+            AppMessage::MatchDerivedScriptsOnline => {
+                let derived_scripts_file = PathBuf::from(self.derived_scripts_file_input.trim());
+                let uspk_set = self.uspk_set.clone();
+                let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir();
+                self.online_match_report = None;
+                self.is_online_match_in_progress = true;
+                return Command::perform(match_derived_scripts_online(derived_scripts_file, uspk_set, data_dir), |match_result| match match_result {
+                    Ok(report) => AppMessage::DerivedScriptsMatchedOnline(report),
+                    Err(e) => AppMessage::OperationFailed(Operation::OnlineMatch, Arc::new(e.into())),
+                });
+            },
+            AppMessage::DerivedScriptsMatchedOnline(report) => {
+                self.is_online_match_in_progress = false;
+                self.online_match_report = Some(report);
+            },
             AppMessage::SearchResultPrepared(search_result) => {
                 self.finds = search_result;
                 self.is_search_in_progress = false;
+                if self.wipe_secrets_after_search {
+                    self.wipe_secrets();
+                }
             },
             AppMessage::CreateClientForGettingDetailsAndThenGet => {
                 let client_setting = self.client_setting.clone();
                 return Command::perform(BitcoincoreRpcClient::new(client_setting), |client_result| {
                 match client_result {
                     Ok(client) => AppMessage::ClientCreatedForGettingDetailsSoGetDetails(client),
-                    Err(e) => AppMessage::Error(Arc::new(e)),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
                 }
             })},
             AppMessage::ClientCreatedForGettingDetailsSoGetDetails(client) => {
                 let finds = self.finds.clone();
+                self.is_getting_details_in_progress = true;
                 return Command::perform(get_details_of_finds_from_bitcoincore(finds, client), |details_result| match details_result {
                     Ok(details) => AppMessage::DetailsReady(details),
-                    Err(e) => AppMessage::Error(Arc::new(e)),
+                    Err(e) => AppMessage::OperationFailed(Operation::GettingDetails, Arc::new(e.into())),
+                });
+            },
+            AppMessage::GetDetailsFromDump => {
+                let Some(selected_dump) = &self.selected_dump else {
+                    self.errors.push(Arc::new(RetrieverError::NoDumpFileInDataDir.into()));
+                    return Command::none();
+                };
+                let finds = self.finds.clone();
+                let dump_file_path = selected_dump.path.clone();
+                self.is_getting_details_in_progress = true;
+                return Command::perform(get_details_of_finds_from_dump(finds, dump_file_path), |details_result| match details_result {
+                    Ok(details) => AppMessage::DetailsReady(details),
+                    Err(e) => AppMessage::OperationFailed(Operation::GettingDetails, Arc::new(e.into())),
                 });
             },
             AppMessage::DetailsReady(details) => {
+                self.is_getting_details_in_progress = false;
                 self.detailed_finds.clone_from(&details);
-                match create_final_finds(details) {
+                match create_final_finds(details, self.explorer_setting_input.get_in_use_network()) {
                     Ok(final_finds) => {
                         self.final_finds = final_finds; 
                     },
-                Err(e) => self.errors.push(Arc::new(e)),
+                Err(e) => self.errors.push(Arc::new(e.into())),
+                }
+            },
+            AppMessage::ToggleFindExpanded(result_num) => {
+                if !self.expanded_finds.remove(&result_num) {
+                    self.expanded_finds.insert(result_num);
+                }
+            },
+            AppMessage::ToggleAddressQrCode(result_num) => {
+                if !self.shown_qr_codes.remove(&result_num) {
+                    self.shown_qr_codes.insert(result_num);
+                }
+            },
+            AppMessage::CopyToClipboard(contents) => return iced::clipboard::write(contents),
+            AppMessage::OpenMessageSigning(result_num) => {
+                self.message_signing_result_num = Some(result_num);
+                self.signed_message = None;
+            },
+            AppMessage::CloseMessageSigning => {
+                self.message_signing_result_num = None;
+                self.signed_message = None;
+            },
+            AppMessage::MessageToSignChanged(message) => self.message_to_sign_input = message,
+            AppMessage::SignMessage => {
+                let Some(final_find) = self.final_finds.iter().find(|final_find| Some(final_find.get_result_num()) == self.message_signing_result_num) else {
+                    return Command::none();
+                };
+                let Some(address) = final_find.get_address() else {
+                    self.signed_message = Some(Err(MessageSigningProblem::UnsupportedAddress));
+                    return Command::none();
+                };
+                let path = final_find.get_path().clone();
+                let address = address.clone();
+                let Some(master_xpriv) = self.master_xpriv() else {
+                    return Command::none();
+                };
+                self.signed_message = Some(sign_message(&master_xpriv, &path, &address, &self.message_to_sign_input));
+            },
+            AppMessage::VerifyAddressChanged(address) => self.verify_address_input = address,
+            AppMessage::VerifyMessageChanged(message) => self.verify_message_input = message,
+            AppMessage::VerifySignatureChanged(signature) => self.verify_signature_input = signature,
+            AppMessage::VerifyMessageSignature => {
+                let network = self.explorer_setting_input.get_in_use_network();
+                self.message_verification = Some(
+                    Address::from_str(self.verify_address_input.trim())
+                        .map_err(|e| e.to_string())
+                        .and_then(|address| address.require_network(network).map_err(|e| e.to_string()))
+                        .map_err(MessageSigningProblem::InvalidAddress)
+                        .and_then(|address| verify_message(&address, &self.verify_message_input, &self.verify_signature_input)),
+                );
+            },
+            AppMessage::ExportResults(format) => {
+                let network = self.explorer_setting_input.get_in_use_network();
+                let source = ResultsSource::new(network, self.selected_dump.as_ref());
+                let report = if self.final_finds.is_empty() {
+                    ResultsReport::from_finds(&self.finds, network, source)
+                } else {
+                    ResultsReport::from_final_finds(&self.final_finds, source)
+                };
+                let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir();
+                return Command::perform(export_results(report, data_dir, format), |export_result| match export_result {
+                    Ok(export_path) => AppMessage::ResultsExported(export_path),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
+                });
+            },
+            AppMessage::ResultsExported(export_path) => self.last_export = Some(export_path),
+            AppMessage::ExportImportDescriptors(ImportDescriptorsVariant::Spendable) => self.pending_spendable_descriptors = Some(SpendableDescriptorsDestination::ExportFile),
+            AppMessage::ExportImportDescriptors(ImportDescriptorsVariant::WatchOnly) => return self.import_descriptors_export_command(ImportDescriptorsVariant::WatchOnly),
+            AppMessage::SpendableDescriptorsExportConfirmed => match self.pending_spendable_descriptors.take() {
+                Some(SpendableDescriptorsDestination::ExportFile) => return self.import_descriptors_export_command(ImportDescriptorsVariant::Spendable),
+                Some(SpendableDescriptorsDestination::CoreWallet) => return self.core_wallet_import_command(),
+                None => {},
+            },
+            AppMessage::SpendableDescriptorsExportDismissed => self.pending_spendable_descriptors = None,
+            AppMessage::ShowWifKeys => self.is_wif_keys_pending = true,
+            AppMessage::WifKeysConfirmationChanged(confirmation) => self.wif_keys_confirmation_input = confirmation,
+            AppMessage::WifKeysDismissed => {
+                self.is_wif_keys_pending = false;
+                self.wif_keys_confirmation_input.clear();
+            },
+            AppMessage::WifKeysConfirmed => {
+                if self.wif_keys_confirmation_input.trim() != WIF_EXPORT_CONFIRMATION_PHRASE {
+                    return Command::none();
                 }
+                self.is_wif_keys_pending = false;
+                self.wif_keys_confirmation_input.clear();
+                let Some(master_xpriv) = self.master_xpriv() else {
+                    return Command::none();
+                };
+                match derive_wif_keys(&self.finds, &master_xpriv, self.explorer_setting_input.get_in_use_network()) {
+                    Ok(wif_keys) => self.wif_keys = wif_keys,
+                    Err(e) => self.errors.push(Arc::new(e.into())),
+                }
+            },
+            AppMessage::CopyWifKeys => {
+                let electrum_keys = Zeroizing::new(self.wif_keys.iter().map(|wif_key| wif_key.electrum_key()).join("\n"));
+                return iced::clipboard::write(electrum_keys.to_string());
+            },
+            // Dropping the keys zeroizes them.
+            AppMessage::WipeWifKeys => self.wif_keys.clear(),
+            AppMessage::ProfilesListed(profile_names) => self.profile_names = profile_names,
+            AppMessage::ProfileSelected(profile_name) => {
+                self.profile_name_input = profile_name.clone();
+                self.selected_profile = Some(profile_name);
+            },
+            AppMessage::LoadProfile => {
+                let Some(profile_name) = self.selected_profile.clone() else {
+                    return Command::none();
+                };
+                return Command::perform(load_settings_profile(profile_name), |load_result| match load_result {
+                    Ok((profile_name, profile)) => AppMessage::ProfileLoaded(profile_name, profile),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
+                });
+            },
+            AppMessage::ProfileLoaded(profile_name, profile) => {
+                profile.apply_to(&mut self.bitcoincore_client_setting_input, &mut self.explorer_setting_input, &mut self.retriever_specific_setting_input);
+                self.loaded_profile = Some(profile_name);
+                self.last_saved_profile = None;
+            },
+            AppMessage::ProfileNameChanged(profile_name) => self.profile_name_input = profile_name,
+            AppMessage::SaveProfile => {
+                let profile_name = self.profile_name_input.trim().to_string();
+                if !is_valid_profile_name(&profile_name) {
+                    return Command::none();
+                }
+                let profile = SettingsProfile::from_inputs(&self.bitcoincore_client_setting_input, &self.explorer_setting_input, &self.retriever_specific_setting_input);
+                self.selected_profile = Some(profile_name.clone());
+                return Command::perform(save_settings_profile(profile_name, profile), |save_result| match save_result {
+                    Ok(profile_path) => AppMessage::ProfileSaved(profile_path),
+                    Err(e) => AppMessage::Error(Arc::new(e.into())),
+                });
+            },
+            AppMessage::ProfileSaved(profile_path) => {
+                self.last_saved_profile = Some(profile_path);
+                self.loaded_profile = None;
+                return Command::perform(list_settings_profiles(), AppMessage::ProfilesListed);
+            },
+            AppMessage::ToggleMnemonicVisibility => self.is_mnemonic_shown = !self.is_mnemonic_shown,
+            AppMessage::ToggleMnemonicWordGrid => {
+                self.mnemonic_word_grid = match self.mnemonic_word_grid {
+                    Some(_) => None,
+                    None => Some(MnemonicWordGrid::from_phrase(self.explorer_setting_input.get_gui_mnemonic())),
+                };
+            },
+            AppMessage::MnemonicWordCountChanged(word_count) => {
+                if let Some(mnemonic_word_grid) = &mut self.mnemonic_word_grid {
+                    mnemonic_word_grid.set_word_count(word_count);
+                    self.explorer_setting_input.set_mnemonic_from_gui_input(mnemonic_word_grid.phrase());
+                }
+            },
+            AppMessage::MnemonicWordChanged(index, word) => {
+                if let Some(mnemonic_word_grid) = &mut self.mnemonic_word_grid {
+                    mnemonic_word_grid.set_word(index, word);
+                    self.explorer_setting_input.set_mnemonic_from_gui_input(mnemonic_word_grid.phrase());
+                }
+            },
+            AppMessage::MnemonicWordSubmitted(index) => {
+                if let Some(mnemonic_word_grid) = &mut self.mnemonic_word_grid {
+                    let is_word_valid = mnemonic_word_grid.complete_word(index);
+                    self.explorer_setting_input.set_mnemonic_from_gui_input(mnemonic_word_grid.phrase());
+                    if is_word_valid && index + 1 < mnemonic_word_grid.word_count() {
+                        return text_input::focus(mnemonic_word_input_id(index + 1));
+                    }
+                }
+            },
+            AppMessage::MnemonicSuggestionChosen(index, suggestion) => {
+                if let Some(mnemonic_word_grid) = &mut self.mnemonic_word_grid {
+                    mnemonic_word_grid.set_word(index, SecretString::new(suggestion.to_string()));
+                    self.explorer_setting_input.set_mnemonic_from_gui_input(mnemonic_word_grid.phrase());
+                    if index + 1 < mnemonic_word_grid.word_count() {
+                        return text_input::focus(mnemonic_word_input_id(index + 1));
+                    }
+                }
+            },
+            AppMessage::TogglePassphraseVisibility => self.is_passphrase_shown = !self.is_passphrase_shown,
+            AppMessage::WipeSecrets => self.wipe_secrets(),
+            AppMessage::WipeSecretsAfterSearchChanged(wipe_after_search) => self.wipe_secrets_after_search = wipe_after_search,
+            AppMessage::WipeSecretsAfterIdleChanged(idle_minutes) => self.wipe_secrets_after_idle_input = idle_minutes,
+            AppMessage::CheckIdleWipe => {
+                let is_busy = self.is_search_in_progress
+                    || self.is_populating_in_progress
+                    || self.is_getting_details_in_progress
+                    || self.is_core_wallet_import_in_progress
+                    || self.is_sweep_in_progress;
+                let is_idle = match (self.wipe_secrets_after_idle(), self.last_activity) {
+                    (Some(idle_limit), Some(last_activity)) => last_activity.elapsed() >= idle_limit,
+                    _ => false,
+                };
+                if is_idle && !is_busy && self.has_secrets() {
+                    self.wipe_secrets();
+                }
+            },
+            AppMessage::CoreWalletNameChanged(wallet_name) => self.core_wallet_name = wallet_name,
+            AppMessage::CoreWalletSpendableChanged(spendable) => self.core_wallet_spendable = spendable,
+            AppMessage::SendFindsToCoreWallet => {
+                if self.core_wallet_spendable {
+                    self.pending_spendable_descriptors = Some(SpendableDescriptorsDestination::CoreWallet);
+                    return Command::none();
+                }
+                return self.core_wallet_import_command();
+            },
+            AppMessage::CoreWalletImportFinished(report) => {
+                self.is_core_wallet_import_in_progress = false;
+                self.core_wallet_import_report = Some(report);
+            },
+            AppMessage::SweepDestinationChanged(destination) => self.sweep_destination_input = destination,
+            AppMessage::SweepFeeRateChanged(fee_rate) => self.sweep_fee_rate_input = fee_rate,
+            AppMessage::GetFeeEstimates => {
+                let client_setting = self.client_setting.clone();
+                self.is_getting_fee_estimates_in_progress = true;
+                return Command::perform(get_fee_estimates(client_setting), |estimates_result| match estimates_result {
+                    Ok(fee_estimates) => AppMessage::FeeEstimatesReady(fee_estimates),
+                    Err(e) => AppMessage::OperationFailed(Operation::GettingFeeEstimates, Arc::new(e.into())),
+                });
+            },
+            AppMessage::FeeEstimatesReady(fee_estimates) => {
+                self.is_getting_fee_estimates_in_progress = false;
+                self.fee_estimates = Some(fee_estimates);
+            },
+            AppMessage::BuildSweepPsbt => {
+                let plan = match self.sweep_plan() {
+                    Ok(plan) => plan,
+                    Err(problem) => {
+                        self.errors.push(Arc::new(problem.into()));
+                        return Command::none();
+                    },
+                };
+                let Some(master_xpriv) = self.master_xpriv() else {
+                    return Command::none();
+                };
+                let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir();
+                let client_setting = self.bitcoincore_client_setting_input.is_input_fixed().then(|| self.client_setting.clone());
+                self.is_sweep_in_progress = true;
+                self.prepared_sweep = None;
+                self.signed_sweep = None;
+                self.sweep_mempool_acceptance = None;
+                return Command::perform(prepare_sweep_psbt(plan, master_xpriv, data_dir, client_setting), |sweep_result| match sweep_result {
+                    Ok(prepared_sweep) => AppMessage::SweepPsbtReady(prepared_sweep),
+                    Err(e) => AppMessage::OperationFailed(Operation::Sweep, Arc::new(e.into())),
+                });
+            },
+            AppMessage::SweepPsbtReady(prepared_sweep) => {
+                self.is_sweep_in_progress = false;
+                self.prepared_sweep = Some(prepared_sweep);
+            },
+            AppMessage::SignSweep => {
+                let Some(prepared_sweep) = self.prepared_sweep.clone() else {
+                    return Command::none();
+                };
+                let Some(master_xpriv) = self.master_xpriv() else {
+                    return Command::none();
+                };
+                let data_dir = self.retriever_specific_setting_input.get_in_use_data_dir();
+                self.is_sweep_in_progress = true;
+                return Command::perform(sign_sweep(prepared_sweep, master_xpriv, data_dir), |sign_result| match sign_result {
+                    Ok(signed_sweep) => AppMessage::SweepSigned(signed_sweep),
+                    Err(e) => AppMessage::OperationFailed(Operation::Sweep, Arc::new(e)),
+                });
+            },
+            AppMessage::SweepSigned(signed_sweep) => {
+                self.is_sweep_in_progress = false;
+                self.signed_sweep = Some(signed_sweep);
+                self.sweep_mempool_acceptance = None;
+            },
+            AppMessage::TestSweepMempoolAcceptance => {
+                let Some(signed_sweep) = &self.signed_sweep else {
+                    return Command::none();
+                };
+                let raw_hex = signed_sweep.raw_hex();
+                let client_setting = self.client_setting.clone();
+                self.is_sweep_in_progress = true;
+                self.sweep_mempool_acceptance = None;
+                return Command::perform(test_sweep_mempool_acceptance(raw_hex, client_setting), |test_result| match test_result {
+                    Ok(acceptance) => AppMessage::SweepMempoolAcceptanceTested(acceptance),
+                    Err(e) => AppMessage::OperationFailed(Operation::Sweep, Arc::new(e.into())),
+                });
+            },
+            AppMessage::SweepMempoolAcceptanceTested(acceptance) => {
+                self.is_sweep_in_progress = false;
+                self.sweep_mempool_acceptance = Some(acceptance);
+            },
+            AppMessage::BroadcastSweep => self.is_sweep_broadcast_pending = true,
+            AppMessage::SweepBroadcastDismissed => self.is_sweep_broadcast_pending = false,
+            AppMessage::SweepBroadcastConfirmed => {
+                self.is_sweep_broadcast_pending = false;
+                let Some(signed_sweep) = &self.signed_sweep else {
+                    return Command::none();
+                };
+                let raw_hex = signed_sweep.raw_hex();
+                let client_setting = self.client_setting.clone();
+                self.is_sweep_in_progress = true;
+                return Command::perform(broadcast_sweep(raw_hex, client_setting), |broadcast_result| match broadcast_result {
+                    Ok(txid) => AppMessage::SweepBroadcast(txid),
+                    Err(e) => AppMessage::OperationFailed(Operation::Sweep, Arc::new(e.into())),
+                });
+            },
+            AppMessage::SweepBroadcast(txid) => {
+                self.is_sweep_in_progress = false;
+                self.sweep_broadcast = Some(SweepBroadcast { txid, status: BroadcastStatus::InMempool });
+            },
+            AppMessage::CheckSweepBroadcastStatus => {
+                let Some(sweep_broadcast) = &self.sweep_broadcast else {
+                    return Command::none();
+                };
+                if self.is_sweep_broadcast_status_check_in_progress {
+                    return Command::none();
+                }
+                let txid = sweep_broadcast.txid;
+                let client_setting = self.client_setting.clone();
+                self.is_sweep_broadcast_status_check_in_progress = true;
+                return Command::perform(get_sweep_broadcast_status(txid, client_setting), |status_result| match status_result {
+                    Ok(status) => AppMessage::SweepBroadcastStatusChecked(status),
+                    Err(e) => AppMessage::OperationFailed(Operation::SweepBroadcastStatusCheck, Arc::new(e.into())),
+                });
+            },
+            AppMessage::SweepBroadcastStatusChecked(status) => {
+                self.is_sweep_broadcast_status_check_in_progress = false;
+                if let Some(sweep_broadcast) = &mut self.sweep_broadcast {
+                    sweep_broadcast.status = status;
+                }
+            },
+            AppMessage::ResultsSortRequested(column) => self.results_table.sort_by(column),
+            AppMessage::ResultsScriptTypeFilterChanged(script_type_filter) => self.results_table.script_type_filter = script_type_filter,
+            AppMessage::ResultsMinAmountChanged(min_amount) => self.results_table.min_amount_input = min_amount,
+            AppMessage::OperationFailed(operation, e) => {
+                // Only the failed operation stops, an error of anything else leaves a running search or sweep alone.
+                match operation {
+                    Operation::Populating => self.is_populating_in_progress = false,
+                    Operation::Search => self.is_search_in_progress = false,
+                    Operation::OnlineMatch => self.is_online_match_in_progress = false,
+                    Operation::GettingDetails => self.is_getting_details_in_progress = false,
+                    Operation::CoreWalletImport => self.is_core_wallet_import_in_progress = false,
+                    Operation::GettingFeeEstimates => self.is_getting_fee_estimates_in_progress = false,
+                    Operation::Sweep => self.is_sweep_in_progress = false,
+                    Operation::SweepBroadcastStatusCheck => self.is_sweep_broadcast_status_check_in_progress = false,
+                }
+                self.errors.push(e.clone()); 
+                error!("{:?}", e);
             },
             AppMessage::Error(e) => {
                 self.errors.push(e.clone()); 
                 error!("{:?}", e);
-                match e.as_ref() {
-                    RetrieverError::NoDumpFileInDataDir => self.is_populating_in_progress = false,
-                    _ => self.is_populating_in_progress = false,
-                }
             },
             AppMessage::None => {},
             AppMessage::StopPopulatingUtxoDB => {
@@ -272,6 +1010,7 @@ impl Application for RetrieverApp {
 
     fn view(&self) -> iced::Element<'_, Self::Message, Self::Theme, iced::Renderer> {
         Column::new()
+            .push(profiles_row(self))
             .push(bitcoincore_client_setting_row(self))
             .push(exploration_setting_row(self))
             .push(retriever_setting_row(self))
@@ -280,6 +1019,20 @@ impl Application for RetrieverApp {
             .into()
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        let broadcast_status_subscription = match &self.sweep_broadcast {
+            Some(sweep_broadcast) if !sweep_broadcast.is_confirmed() => {
+                iced::time::every(CONFIRMATION_CHECK_INTERVAL).map(|_| AppMessage::CheckSweepBroadcastStatus)
+            },
+            _ => Subscription::none(),
+        };
+        let idle_wipe_subscription = match self.wipe_secrets_after_idle() {
+            Some(_) if self.has_secrets() => iced::time::every(IDLE_WIPE_CHECK_INTERVAL).map(|_| AppMessage::CheckIdleWipe),
+            _ => Subscription::none(),
+        };
+        Subscription::batch([broadcast_status_subscription, idle_wipe_subscription])
+    }
+
     fn theme(&self) -> Self::Theme {
         iced::Theme::TokyoNight
     }
@@ -1,17 +1,54 @@
-use bitceptron_retriever_gui::RetrieverApp;
+use bitceptron_retriever_gui::{
+    api::run_api_server,
+    cli::CliArgs,
+    headless::{output_headless_report, run_headless},
+    RetrieverApp,
+};
 use iced::{font, Application, Pixels};
+use tracing::error;
 use tracing_log::LogTracer;
 
 fn main() {
+    let args = CliArgs::parse();
     LogTracer::init().unwrap();
+    if args.api {
+        tracing::subscriber::set_global_default(
+            tracing_subscriber::fmt()
+                .with_writer(std::io::stderr)
+                .finish(),
+        )
+        .unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        if let Err(e) = runtime.block_on(run_api_server(args)) {
+            error!("{:?}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if args.headless {
+        // Logs go to stderr so stdout holds nothing but the json results.
+        tracing::subscriber::set_global_default(
+            tracing_subscriber::fmt()
+                .with_writer(std::io::stderr)
+                .finish(),
+        )
+        .unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let headless_result = runtime
+            .block_on(run_headless(&args))
+            .and_then(|report| output_headless_report(&args, &report));
+        if let Err(e) = headless_result {
+            error!("{:?}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
     tracing::subscriber::set_global_default(tracing_subscriber::FmtSubscriber::new()).unwrap();
-    let settings = iced::Settings {
-        default_font: iced::Font {
-            family: font::Family::Monospace,
-            ..Default::default()
-        },
-        default_text_size: Pixels::from(12),
+    let mut settings = iced::Settings::with_flags(args);
+    settings.default_font = iced::Font {
+        family: font::Family::Monospace,
         ..Default::default()
     };
+    settings.default_text_size = Pixels::from(12);
     let _ = RetrieverApp::run(settings);
 }
@@ -0,0 +1,104 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc, time::Duration};
+
+use bitceptron_retriever::{client::client_setting::ClientSetting, error::RetrieverError};
+use bitcoincore_rpc::{jsonrpc::serde_json::Value, Auth, RpcApi};
+use serde::de::DeserializeOwned;
+use tracing::{error, info};
+
+/// What bitcoincore answers for a transaction it does not know of.
+pub const RPC_INVALID_ADDRESS_OR_KEY: i32 = -5;
+
+/// The error code of a call that bitcoincore answered with an error.
+pub fn rpc_error_code(error: &RetrieverError) -> Option<i32> {
+    match error {
+        RetrieverError::BitcoincoreRpcCrateError(bitcoincore_rpc::Error::JsonRpc(
+            bitcoincore_rpc::jsonrpc::Error::Rpc(rpc_error),
+        )) => Some(rpc_error.code),
+        _ => None,
+    }
+}
+
+/// The same setting with a longer timeout, for calls that keep bitcoincore busy for minutes. A longer timeout
+/// already set by the user is kept.
+pub fn client_setting_with_timeout(setting: &ClientSetting, timeout_seconds: u64) -> ClientSetting {
+    ClientSetting::new(
+        setting.get_rpc_url(),
+        setting.get_rpc_port(),
+        setting.get_cookie_path(),
+        timeout_seconds.max(*setting.get_timeout_seconds()),
+    )
+}
+
+/// A thin rpc client for the bitcoincore calls that `BitcoincoreRpcClient` does not expose.
+#[derive(Debug, Clone)]
+pub struct NodeRpcClient {
+    client: Arc<bitcoincore_rpc::Client>,
+}
+
+impl NodeRpcClient {
+    pub async fn new(setting: ClientSetting) -> Result<Self, RetrieverError> {
+        Self::connect(setting, None).await
+    }
+
+    /// A client whose calls go to the `/wallet/{wallet_name}` endpoint, as wallet rpcs need with several wallets loaded.
+    pub async fn new_for_wallet(
+        setting: ClientSetting,
+        wallet_name: &str,
+    ) -> Result<Self, RetrieverError> {
+        Self::connect(setting, Some(format!("/wallet/{}", wallet_name))).await
+    }
+
+    async fn connect(
+        setting: ClientSetting,
+        endpoint: Option<String>,
+    ) -> Result<Self, RetrieverError> {
+        info!("Creation of node rpc client started.");
+        let (user, pass) = Auth::CookieFile(PathBuf::from_str(setting.get_cookie_path()).unwrap())
+            .get_user_pass()?;
+        let client = tokio::task::spawn_blocking(move || {
+            let jsonrpc_build = bitcoincore_rpc::jsonrpc::simple_http::Builder::new()
+                .timeout(Duration::from_secs(*setting.get_timeout_seconds()))
+                .auth(user.unwrap_or_default(), pass)
+                .url(
+                    format!(
+                        "{}:{}{}",
+                        setting.get_rpc_url(),
+                        setting.get_rpc_port(),
+                        endpoint.unwrap_or_default()
+                    )
+                    .as_str(),
+                )?
+                .build();
+            let jsonrpc_client = bitcoincore_rpc::jsonrpc::Client::from(jsonrpc_build);
+            let client = bitcoincore_rpc::Client::from_jsonrpc(jsonrpc_client);
+            match client.ping() {
+                Ok(_) => {
+                    info!("Node rpc client responded successfully to ping.");
+                    Ok(client)
+                }
+                Err(_) => {
+                    error!("Node rpc client did not respond to the ping.");
+                    Err(RetrieverError::BitcoincoreRpcUnreachable)
+                }
+            }
+        })
+        .await??;
+        Ok(NodeRpcClient {
+            client: Arc::new(client),
+        })
+    }
+
+    pub async fn call<T: DeserializeOwned + Send + 'static>(
+        &self,
+        command: &'static str,
+        args: Vec<Value>,
+    ) -> Result<T, RetrieverError> {
+        let client = self.client.clone();
+        let response = tokio::task::spawn_blocking(move || {
+            info!("Sending {} to bitcoincore.", command);
+            client.call::<T>(command, &args)
+        })
+        .await?;
+        Ok(response?)
+    }
+}
@@ -1,14 +1,22 @@
 use std::{fs, path::PathBuf, str::FromStr, sync::Arc};
 
 use bitceptron_retriever::{
-    client::{client_setting::ClientSetting, BitcoincoreRpcClient},
+    client::{
+        client_setting::ClientSetting, dump_utxout_set_result::DumpTxoutSetResult,
+        BitcoincoreRpcClient,
+    },
     covered_descriptors::CoveredDescriptors,
     error::RetrieverError,
     explorer::Explorer,
-    path_pairs::{PathDescriptorPair, PathScanResultDescriptorTrio},
+    path_pairs::PathScanRequestDescriptorTrio,
     setting::RetrieverSetting,
 };
-use bitcoin::{bip32::DerivationPath, key::Secp256k1};
+use bitcoin::{
+    bip32::{DerivationPath, Xpriv},
+    key::Secp256k1,
+    Network, PublicKey, Transaction, Txid,
+};
+use bitcoincore_rpc::jsonrpc::serde_json::Value;
 use itertools::Itertools;
 use miniscript::Descriptor;
 use num_format::{Locale, ToFormattedString};
@@ -16,30 +24,72 @@ use tokio::{join, sync::mpsc};
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
-use crate::{uspk_set::UnspentScriptPubKeysSet, view_elements::final_finds::FinalFinds, RetrieverApp};
+use crate::{
+    domain::{
+        broadcast::{BroadcastStatus, MempoolAcceptance},
+        core_wallet::{
+            CoreWalletImportReport, CoreWalletRequest, DescriptorImportResult, WalletBalance,
+        },
+        descriptor_export::{
+            build_import_descriptor_requests, write_import_descriptors, FindToImport,
+            ImportDescriptorsVariant,
+        },
+        dump_catalog::{
+            delete_dump_file, dump_file_name_for_height, latest_dump_for_chain, list_dump_files,
+            network_dir_in_data_dir, DumpFileEntry, LEGACY_DUMP_FILE_NAME,
+        },
+        dump_integrity::DumpReadSummary,
+        dump_metadata::DumpMetadata,
+        dump_preflight::{free_space_in_bytes, DumpPreflightReport},
+        fee_estimates::{FeeEstimates, FeePriority},
+        finds::{DetailedFind, FoundUtxo, PathDescriptorFind},
+        profiles::{list_profiles, profiles_dir, SettingsProfile},
+        results_export::{export_file_path, write_export_file, ExportFormat, ResultsReport},
+        snapshot_coins::SnapshotCoins,
+        snapshot_header::{InspectedSnapshot, SnapshotHeader},
+        split_workflow::{
+            descriptor_for, write_online_matches, DerivedScriptRecord, DerivedScripts,
+            DerivedScriptsWriter, OfflineDerivationSummary, OnlineMatchReport,
+        },
+        sweep::{PreparedSweep, SignedSweep, SweepPlan},
+    },
+    gui_error::GuiError,
+    node_client::{
+        client_setting_with_timeout, rpc_error_code, NodeRpcClient, RPC_INVALID_ADDRESS_OR_KEY,
+    },
+    uspk_set::UnspentScriptPubKeysSet,
+    view_elements::final_finds::FinalFinds,
+    RetrieverApp,
+};
+
+/// How long `gettxoutsetinfo` may take in the dump preflight.
+const UTXO_SET_INFO_TIMEOUT_SECONDS: u64 = 30 * 60;
+/// How long `importdescriptors` may take. A rescan from an early block on mainnet can take hours.
+const IMPORT_DESCRIPTORS_TIMEOUT_SECONDS: u64 = 24 * 60 * 60;
 
 pub fn create_client_setting(app: &RetrieverApp) -> ClientSetting {
     app.bitcoincore_client_setting_input.to_client_setting()
 }
 
 pub fn create_retriever_setting(app: &mut RetrieverApp) -> RetrieverSetting {
+    let explorer_setting = app.explorer_setting_input.to_explorer_setting();
     RetrieverSetting::new(
         Some(app.client_setting.get_rpc_url().to_owned()),
         Some(app.client_setting.get_rpc_port().to_owned()),
         app.client_setting.get_cookie_path().to_owned(),
         Some(app.client_setting.get_timeout_seconds().to_owned()),
-        app.explorer_setting.get_mnemonic().to_owned(),
-        app.explorer_setting.get_passphrase().to_owned(),
-        Some(app.explorer_setting.get_base_derivation_paths().to_owned()),
-        Some(app.explorer_setting.get_exploration_path().to_owned()),
+        explorer_setting.get_mnemonic().to_owned(),
+        explorer_setting.get_passphrase().to_owned(),
+        Some(explorer_setting.get_base_derivation_paths().to_owned()),
+        Some(explorer_setting.get_exploration_path().to_owned()),
         Some(Vec::from_iter(
             app.retriever_specific_setting_input
                 .get_in_use_selected_descriptors()
                 .clone(),
         )),
-        Some(app.explorer_setting.get_sweep().to_owned()),
-        Some(app.explorer_setting.get_exploration_depth().to_owned()),
-        Some(app.explorer_setting.get_network().to_owned()),
+        Some(explorer_setting.get_sweep().to_owned()),
+        Some(explorer_setting.get_exploration_depth().to_owned()),
+        Some(explorer_setting.get_network().to_owned()),
         app.retriever_specific_setting_input
             .get_in_use_data_dir()
             .to_owned(),
@@ -49,68 +99,287 @@ pub fn create_retriever_setting(app: &mut RetrieverApp) -> RetrieverSetting {
 pub async fn check_for_dump_in_data_dir_or_create_dump_file(
     data_dir: String,
     client: BitcoincoreRpcClient,
-) -> Result<(), RetrieverError> {
-    let res = tokio::join!(async {
-        let data_dir_path = PathBuf::from_str(&data_dir).unwrap();
-        let mut dump_file_path = data_dir_path.clone();
-        dump_file_path.extend(["utxo_dump.dat"]);
-        info!("Searching for the dump file in datadir.");
-        if dump_file_path.exists() {
+    client_setting: ClientSetting,
+) -> Result<DumpFileEntry, RetrieverError> {
+    info!("Searching for a dump file of the node's chain in datadir.");
+    match find_latest_dump_for_node(data_dir.clone(), client_setting.clone()).await? {
+        Some(entry) => {
             info!("Dump file found in datadir.");
-            Ok(())
-        } else {
+            Ok(entry)
+        }
+        None => {
             info!("Dump file was not found in datadir.");
-            if !data_dir_path.exists() {
-                info!("Creating the full datadir path.");
-                fs::create_dir_all(data_dir_path)?;
-            }
-            let _dump_result = client.dump_utxo_set(&data_dir).await?;
-            Ok(())
+            create_new_dump_in_data_dir(data_dir, client, client_setting).await
         }
-    });
-    res.0
+    }
+}
+
+pub async fn get_node_chain(client_setting: ClientSetting) -> Result<String, RetrieverError> {
+    let client = NodeRpcClient::new(client_setting).await?;
+    let blockchain_info: Value = client.call("getblockchaininfo", vec![]).await?;
+    Ok(blockchain_info["chain"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string())
+}
+
+/// The newest dump in the data dir taken from the same chain the node is on, with its metadata filled in.
+pub async fn find_latest_dump_for_node(
+    data_dir: String,
+    client_setting: ClientSetting,
+) -> Result<Option<DumpFileEntry>, RetrieverError> {
+    let chain = get_node_chain(client_setting.clone()).await?;
+    match latest_dump_for_chain(&data_dir, &chain) {
+        Some(mut entry) => {
+            entry.metadata =
+                Some(read_or_reconstruct_dump_metadata(entry.path.clone(), client_setting).await?);
+            Ok(Some(entry))
+        }
+        None => Ok(None),
+    }
+}
+
+pub async fn list_dump_files_in_data_dir(data_dir: String) -> Vec<DumpFileEntry> {
+    tokio::task::spawn_blocking(move || list_dump_files(&data_dir))
+        .await
+        .unwrap_or_default()
+}
+
+/// Builds the catalog entry of a single dump file, reading or reconstructing its metadata.
+pub async fn describe_dump_file(
+    dump_file_path: PathBuf,
+    client_setting: ClientSetting,
+) -> Result<DumpFileEntry, RetrieverError> {
+    let network_dir = dump_file_path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| name.to_string_lossy().to_string());
+    let mut entry = DumpFileEntry::from_path(&dump_file_path, network_dir)
+        .ok_or(RetrieverError::NoDumpFileInDataDir)?;
+    entry.metadata = Some(read_or_reconstruct_dump_metadata(dump_file_path, client_setting).await?);
+    Ok(entry)
+}
+
+pub async fn inspect_snapshot_file(
+    snapshot_file_path: PathBuf,
+) -> Result<InspectedSnapshot, RetrieverError> {
+    info!(
+        "Reading the header of snapshot file {}.",
+        snapshot_file_path.display()
+    );
+    tokio::task::spawn_blocking(move || {
+        let header = SnapshotHeader::read(&snapshot_file_path)?;
+        info!("Snapshot header read: {}", header);
+        Ok(InspectedSnapshot {
+            path: snapshot_file_path,
+            header,
+        })
+    })
+    .await?
+}
+
+pub async fn delete_dump_file_from_data_dir(dump_file_path: PathBuf) -> Result<(), RetrieverError> {
+    info!("Deleting dump file {}.", dump_file_path.display());
+    Ok(delete_dump_file(&dump_file_path)?)
+}
+
+pub async fn run_dump_preflight(
+    client_setting: ClientSetting,
+    data_dir: String,
+) -> Result<DumpPreflightReport, RetrieverError> {
+    info!("Running preflight checks before dumping the utxo set.");
+    let client = NodeRpcClient::new(client_setting.clone()).await?;
+    let blockchain_info: Value = client.call("getblockchaininfo", vec![]).await?;
+    info!("Asking bitcoincore for the utxo set size. This may take a while on nodes without coinstatsindex.");
+    // Without coinstatsindex this walks the whole utxo set, which outlasts the usual rpc timeout on mainnet.
+    let utxo_set_info_client = NodeRpcClient::new(client_setting_with_timeout(
+        &client_setting,
+        UTXO_SET_INFO_TIMEOUT_SECONDS,
+    ))
+    .await?;
+    let utxo_count = utxo_set_info_client
+        .call::<Value>("gettxoutsetinfo", vec![Value::String("none".to_string())])
+        .await
+        .map_err(|e| warn!("Could not get the utxo set size: {:?}", e))
+        .ok()
+        .and_then(|info| info["txouts"].as_u64());
+    let report = DumpPreflightReport {
+        chain: blockchain_info["chain"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        blocks: blockchain_info["blocks"].as_u64().unwrap_or_default(),
+        headers: blockchain_info["headers"].as_u64().unwrap_or_default(),
+        initial_block_download: blockchain_info["initialblockdownload"]
+            .as_bool()
+            .unwrap_or_default(),
+        verification_progress: blockchain_info["verificationprogress"]
+            .as_f64()
+            .unwrap_or_default(),
+        pruned: blockchain_info["pruned"].as_bool().unwrap_or_default(),
+        prune_height: blockchain_info["pruneheight"].as_u64(),
+        utxo_count,
+        free_space_in_bytes: free_space_in_bytes(&data_dir),
+    };
+    for problem in report.problems() {
+        warn!("Dump preflight: {}", problem);
+    }
+    Ok(report)
 }
 
 pub async fn create_new_dump_in_data_dir(
     data_dir: String,
     client: BitcoincoreRpcClient,
-) -> Result<(), RetrieverError> {
-    let res = tokio::join!(async {
-        let data_dir_path = PathBuf::from_str(&data_dir).unwrap();
-        let mut dump_file_path = data_dir_path.clone();
-        dump_file_path.extend(["utxo_dump.dat"]);
-        info!("Searching for the dump file in datadir.");
-        if dump_file_path.exists() {
-            let _ = fs::remove_file(dump_file_path);
-            info!("Dump file found and deleted.");
-        }
-        info!("Dump file was not found in datadir.");
-        if !data_dir_path.exists() {
-            info!("Creating the full datadir path.");
-            fs::create_dir_all(data_dir_path)?;
+    client_setting: ClientSetting,
+) -> Result<DumpFileEntry, RetrieverError> {
+    let chain = get_node_chain(client_setting.clone()).await?;
+    let network_dir_path = network_dir_in_data_dir(&data_dir, &chain);
+    let mut fresh_dump_file_path = network_dir_path.clone();
+    fresh_dump_file_path.push(LEGACY_DUMP_FILE_NAME);
+    if fresh_dump_file_path.exists() {
+        let _ = fs::remove_file(&fresh_dump_file_path);
+        info!("Leftover dump file found and deleted.");
+    }
+    if !network_dir_path.exists() {
+        info!("Creating the full datadir path.");
+        fs::create_dir_all(&network_dir_path)?;
+    }
+    let dump_result = client
+        .dump_utxo_set(network_dir_path.to_str().unwrap())
+        .await?;
+    let mut dump_file_path = network_dir_path;
+    dump_file_path.push(dump_file_name_for_height(*dump_result.get_base_height()));
+    if dump_file_path.exists() {
+        info!("Replacing the older dump file of the same height.");
+        delete_dump_file(&dump_file_path)?;
+    }
+    fs::rename(&fresh_dump_file_path, &dump_file_path)?;
+    let metadata =
+        record_dump_metadata(dump_file_path.clone(), &dump_result, client_setting).await?;
+    let mut entry = DumpFileEntry::from_path(&dump_file_path, Some(chain))
+        .ok_or(RetrieverError::NoDumpFileInDataDir)?;
+    entry.metadata = Some(metadata);
+    Ok(entry)
+}
+
+/// Writes the sidecar metadata of a freshly created dump, enriched with what the node knows about its base block.
+pub async fn record_dump_metadata(
+    dump_file_path: PathBuf,
+    dump_result: &DumpTxoutSetResult,
+    client_setting: ClientSetting,
+) -> Result<DumpMetadata, RetrieverError> {
+    let base_block_info =
+        get_base_block_info(client_setting, dump_result.get_base_hash().to_owned()).await;
+    let metadata = DumpMetadata::from_dump_result(
+        dump_result,
+        base_block_info.chain,
+        base_block_info.time,
+        base_block_info.node_version,
+    );
+    metadata.write_sidecar(&dump_file_path)?;
+    info!("Dump metadata recorded: {}", metadata);
+    Ok(metadata)
+}
+
+/// Reads the sidecar metadata of a dump, or rebuilds what it can from the dump header and the node.
+pub async fn read_or_reconstruct_dump_metadata(
+    dump_file_path: PathBuf,
+    client_setting: ClientSetting,
+) -> Result<DumpMetadata, RetrieverError> {
+    if let Some(metadata) = DumpMetadata::read_sidecar(&dump_file_path) {
+        info!("Dump metadata read: {}", metadata);
+        return Ok(metadata);
+    }
+    warn!("Dump file has no metadata sidecar. Reconstructing it from the dump header.");
+    let header = SnapshotHeader::read(&dump_file_path)?;
+    let base_hash = header.block_hash.to_string();
+    let base_block_info = get_base_block_info(client_setting, base_hash.clone()).await;
+    let metadata = DumpMetadata {
+        base_height: base_block_info.height,
+        base_hash,
+        coins_written: header.coin_count,
+        txoutset_hash: None,
+        nchaintx: None,
+        chain: base_block_info.chain,
+        base_block_time: base_block_info.time,
+        created_at: chrono::Utc::now().timestamp(),
+        node_version: None,
+    };
+    if let Err(e) = metadata.write_sidecar(&dump_file_path) {
+        warn!("Could not write the dump metadata sidecar: {:?}", e);
+    }
+    Ok(metadata)
+}
+
+#[derive(Debug, Default)]
+struct BaseBlockInfo {
+    chain: Option<String>,
+    time: Option<i64>,
+    height: Option<u64>,
+    node_version: Option<u64>,
+}
+
+/// Best effort lookup of the chain name, the node version and the time and height of a block. Any failure yields `None`s.
+async fn get_base_block_info(client_setting: ClientSetting, block_hash: String) -> BaseBlockInfo {
+    let client = match NodeRpcClient::new(client_setting).await {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("Could not reach bitcoincore for dump metadata: {:?}", e);
+            return BaseBlockInfo::default();
         }
-        let _dump_result = client.dump_utxo_set(&data_dir).await?;
-        Ok(())
-    });
-    res.0
+    };
+    let chain = client
+        .call::<Value>("getblockchaininfo", vec![])
+        .await
+        .ok()
+        .and_then(|info| info["chain"].as_str().map(|chain| chain.to_string()));
+    let header = client
+        .call::<Value>("getblockheader", vec![Value::String(block_hash)])
+        .await
+        .ok();
+    let node_version = client
+        .call::<Value>("getnetworkinfo", vec![])
+        .await
+        .ok()
+        .and_then(|info| info["version"].as_u64());
+    BaseBlockInfo {
+        chain,
+        time: header.as_ref().and_then(|header| header["time"].as_i64()),
+        height: header.as_ref().and_then(|header| header["height"].as_u64()),
+        node_version,
+    }
+}
+
+pub async fn get_node_tip_height(client_setting: ClientSetting) -> Result<u64, RetrieverError> {
+    let client = NodeRpcClient::new(client_setting).await?;
+    client.call::<u64>("getblockcount", vec![]).await
 }
 
 pub async fn populate_uspk_set(
-    data_dir: String,
+    dump_file_path: PathBuf,
+    compute_txoutset_hash: bool,
     cancellation_token: CancellationToken,
-) -> Result<UnspentScriptPubKeysSet, RetrieverError> {
+) -> Result<(UnspentScriptPubKeysSet, DumpReadSummary), RetrieverError> {
     let mut uspk_set = UnspentScriptPubKeysSet::new();
     info!("Searching for the dump file to populate the Unspent ScriptPubKey set.");
-    let dump_file_path_str = format!("{}/utxo_dump.dat", data_dir);
-    let dump_file_path = PathBuf::from_str(&dump_file_path_str).unwrap();
     if !dump_file_path.exists() {
-        error!("Dump file (utxo_dump.dat) does not exist in data dir.");
+        error!("Dump file {} does not exist.", dump_file_path.display());
         return Err(RetrieverError::NoDumpFileInDataDir);
     }
     info!("Dump file found.");
+    if !SnapshotHeader::read(&dump_file_path)?.is_supported() {
+        error!(
+            "Dump file {} uses an unsupported snapshot format.",
+            dump_file_path.display()
+        );
+        return Err(RetrieverError::ConsensusEncodeError(
+            bitcoin::consensus::encode::Error::ParseFailed("unsupported snapshot format"),
+        ));
+    }
+    let dump_file_path_str = dump_file_path.to_string_lossy().to_string();
     tokio::select!(
-        _ = uspk_set.populate_with_dump_file(&dump_file_path_str, cancellation_token.clone()) => { Ok(uspk_set) },
-        _ = cancellation_token.cancelled() => { Ok(UnspentScriptPubKeysSet::new())},
+        summary = uspk_set.populate_with_dump_file(&dump_file_path_str, compute_txoutset_hash, cancellation_token.clone()) => { Ok((uspk_set, summary?)) },
+        _ = cancellation_token.cancelled() => { Ok((UnspentScriptPubKeysSet::new(), DumpReadSummary::default()))},
     )
 }
 
@@ -137,13 +406,20 @@ pub async fn create_derivation_path_stream(
             .multi_cartesian_product();
         for explore_path in explore_paths_iter {
             for base in bases.iter() {
-                sender
+                // The receiver is gone once the search is cancelled, so there is no one left to send to.
+                if sender
                     .send(base.extend(
                         DerivationPath::from_str(&format!("m/{}", explore_path.join("/"))).unwrap(),
                     ))
                     .await
-                    .map_err(|e| error!("{:?}", e))
-                    .unwrap();
+                    .is_err()
+                {
+                    info!(
+                        "Path stream stopped after {} paths.",
+                        sent_paths.to_formatted_string(&Locale::en)
+                    );
+                    return;
+                }
                 sent_paths += 1;
                 if sent_paths % 1000 == 0 {
                     info!(
@@ -162,7 +438,7 @@ pub async fn process_derivation_path_stream(
     uspk_set: Arc<UnspentScriptPubKeysSet>,
     explorer: Arc<Explorer>,
     receiver: &mut mpsc::Receiver<DerivationPath>,
-) -> Vec<PathDescriptorPair> {
+) -> Vec<PathDescriptorFind> {
     let secp = Secp256k1::new();
     let uspk_set = uspk_set.get_immutable_inner_set();
     let mut paths_received = 0;
@@ -188,7 +464,7 @@ pub async fn process_derivation_path_stream(
             let target = desc_pubkey.as_bytes();
             if uspk_set.contains(target) {
                 warn!("Found a UTXO match for ScriptPubKey.");
-                finds.push(PathDescriptorPair::new(path.to_owned(), desc));
+                finds.push(PathDescriptorFind::new(path.to_owned(), desc));
             }
         }
         if select_descriptors.contains(&CoveredDescriptors::P2pkh) {
@@ -199,7 +475,7 @@ pub async fn process_derivation_path_stream(
             let target = desc_pubkey.as_bytes();
             if uspk_set.contains(target) {
                 warn!("Found a UTXO match for ScriptPubKey.");
-                finds.push(PathDescriptorPair::new(path.to_owned(), desc));
+                finds.push(PathDescriptorFind::new(path.to_owned(), desc));
             }
         }
         if select_descriptors.contains(&CoveredDescriptors::P2wpkh) {
@@ -210,7 +486,7 @@ pub async fn process_derivation_path_stream(
             let target = desc_pubkey.as_bytes();
             if uspk_set.contains(target) {
                 warn!("Found a UTXO match for ScriptPubKey.");
-                finds.push(PathDescriptorPair::new(path.to_owned(), desc));
+                finds.push(PathDescriptorFind::new(path.to_owned(), desc));
             }
         }
         if select_descriptors.contains(&CoveredDescriptors::P2shwpkh) {
@@ -221,7 +497,7 @@ pub async fn process_derivation_path_stream(
             let target = desc_pubkey.as_bytes();
             if uspk_set.contains(target) {
                 warn!("Found a UTXO match for ScriptPubKey.");
-                finds.push(PathDescriptorPair::new(path.to_owned(), desc));
+                finds.push(PathDescriptorFind::new(path.to_owned(), desc));
             }
         }
         if select_descriptors.contains(&CoveredDescriptors::P2tr) {
@@ -232,7 +508,7 @@ pub async fn process_derivation_path_stream(
             let target = desc_pubkey.as_bytes();
             if uspk_set.contains(target) {
                 warn!("Found a UTXO match for ScriptPubKey.");
-                finds.push(PathDescriptorPair::new(path.to_owned(), desc));
+                finds.push(PathDescriptorFind::new(path.to_owned(), desc));
             }
         }
     }
@@ -244,7 +520,7 @@ pub async fn search_process_of_uspk_set(
     select_descriptors: hashbrown::HashSet<CoveredDescriptors>,
     uspk_set: Arc<UnspentScriptPubKeysSet>,
     explorer: Arc<Explorer>,
-) -> Vec<PathDescriptorPair> {
+) -> Vec<PathDescriptorFind> {
     let explorer_clone = explorer.clone();
     let (tx_stream, mut rx_stream) = mpsc::channel(2048);
     let (_, res) = join!(
@@ -264,7 +540,7 @@ pub async fn search_the_uspk_set(
     uspk_set: Arc<UnspentScriptPubKeysSet>,
     explorer: Arc<Explorer>,
     cancellation_token: CancellationToken,
-) -> Vec<PathDescriptorPair> {
+) -> Vec<PathDescriptorFind> {
     let explorer_clone = explorer.clone();
     let (tx_os, rx_os) = tokio::sync::oneshot::channel();
     tokio::select! {
@@ -276,36 +552,505 @@ pub async fn search_the_uspk_set(
         }
 
     }
-    rx_os.await.unwrap()
+    // A cancelled search sends nothing and comes back without finds.
+    rx_os.await.unwrap_or_default()
 }
 
 pub async fn get_details_of_finds_from_bitcoincore(
-    finds: Vec<PathDescriptorPair>,
+    finds: Vec<PathDescriptorFind>,
     client: BitcoincoreRpcClient,
-) -> Result<Option<Vec<PathScanResultDescriptorTrio>>, RetrieverError> {
+) -> Result<Option<Vec<DetailedFind>>, RetrieverError> {
     if finds.is_empty() {
         println!("No UTXO match were found in the explored paths.");
         Ok(None)
     } else {
-        let path_scan_request_pairs = finds
+        let path_scan_request_pairs: Vec<PathScanRequestDescriptorTrio> = finds
             .iter()
-            .map(|item| item.to_path_scan_request_descriptor_trio())
+            .map(|item| {
+                item.to_path_descriptor_pair()
+                    .to_path_scan_request_descriptor_trio()
+            })
             .collect();
         let detailed_finds = tokio::join!(client.scan_utxo_set(path_scan_request_pairs)).0?;
-        Ok(Some(detailed_finds))
+        Ok(Some(
+            detailed_finds
+                .iter()
+                .map(DetailedFind::from_scan_result)
+                .collect(),
+        ))
     }
 }
 
+/// Builds the details of the finds from a second pass over the dump, without asking bitcoincore.
+/// Amounts and outpoints are those at the dump's base height.
+pub async fn get_details_of_finds_from_dump(
+    finds: Vec<PathDescriptorFind>,
+    dump_file_path: PathBuf,
+) -> Result<Option<Vec<DetailedFind>>, RetrieverError> {
+    if finds.is_empty() {
+        info!("No UTXO match were found in the explored paths.");
+        return Ok(None);
+    }
+    let at_height =
+        DumpMetadata::read_sidecar(&dump_file_path).and_then(|metadata| metadata.base_height);
+    let mut dump = SnapshotCoins::open(&dump_file_path)?;
+    let details_start = std::time::Instant::now();
+    tokio::task::spawn_blocking(move || {
+        let mut unspents_by_script: hashbrown::HashMap<Vec<u8>, Vec<FoundUtxo>> = finds
+            .iter()
+            .map(|find| (find.script_pubkey().to_bytes(), vec![]))
+            .collect();
+        for txout in dump.by_ref() {
+            if let Some(unspents) = unspents_by_script.get_mut(txout.script_pubkey.as_bytes()) {
+                unspents.push(FoundUtxo {
+                    out_point: txout.out_point,
+                    amount_in_sat: u64::from(txout.amount),
+                    height: txout.height as u64,
+                    is_coinbase: Some(txout.is_coinbase),
+                });
+            }
+        }
+        info!(
+            "Details of {} finds read from the dump in {} seconds.",
+            finds.len(),
+            details_start.elapsed().as_secs()
+        );
+        // A script found under more than one path takes its unspents to the first find only, so totals count
+        // each of them once.
+        Ok(Some(
+            finds
+                .iter()
+                .filter_map(|find| {
+                    let unspents = unspents_by_script.remove(find.script_pubkey().as_bytes())?;
+                    Some(DetailedFind::from_find(find, unspents, at_height))
+                })
+                .collect(),
+        ))
+    })
+    .await
+    .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?
+}
+
 pub fn create_final_finds(
-    detailed_finds: Option<Vec<PathScanResultDescriptorTrio>>,
+    detailed_finds: Option<Vec<DetailedFind>>,
+    network: bitcoin::Network,
 ) -> Result<Vec<FinalFinds>, RetrieverError> {
     if detailed_finds.is_none() {
         return Err(RetrieverError::DetailsHaveNotBeenFetched);
     };
     let mut res = vec![];
     for (index, detail) in detailed_finds.unwrap().iter().enumerate() {
-        let info = FinalFinds::new((index + 1) as u64, detail);
+        let info = FinalFinds::new((index + 1) as u64, detail, network);
         res.push(info);
     }
     Ok(res)
 }
+
+pub async fn export_results(
+    report: ResultsReport,
+    data_dir: String,
+    format: ExportFormat,
+) -> Result<PathBuf, RetrieverError> {
+    let export_path = report.write(&data_dir, format)?;
+    info!("Results exported to {}.", export_path.to_string_lossy());
+    Ok(export_path)
+}
+
+/// Writes the finds as `importdescriptors` json. The rescan timestamp of each descriptor is the time of the block
+/// holding its earliest unspent, looked up on the node when it is reachable. Otherwise the whole chain is rescanned.
+pub async fn export_import_descriptors(
+    finds_with_earliest_height: Vec<(PathDescriptorFind, Option<u64>)>,
+    master_xpriv: Xpriv,
+    variant: ImportDescriptorsVariant,
+    data_dir: String,
+    client_setting: Option<ClientSetting>,
+) -> Result<PathBuf, RetrieverError> {
+    let client = match client_setting {
+        Some(client_setting) => NodeRpcClient::new(client_setting).await.ok(),
+        None => None,
+    };
+    let finds_to_import =
+        resolve_finds_to_import(client.as_ref(), finds_with_earliest_height).await;
+    let requests = build_import_descriptor_requests(&finds_to_import, &master_xpriv, variant)?;
+    let export_path = write_import_descriptors(&requests, &data_dir, variant)?;
+    info!(
+        "{} importdescriptors exported to {}.",
+        variant,
+        export_path.to_string_lossy()
+    );
+    Ok(export_path)
+}
+
+async fn resolve_finds_to_import(
+    client: Option<&NodeRpcClient>,
+    finds_with_earliest_height: Vec<(PathDescriptorFind, Option<u64>)>,
+) -> Vec<FindToImport> {
+    let mut finds_to_import = vec![];
+    for (find, earliest_height) in finds_with_earliest_height {
+        let earliest_block_time = match (client, earliest_height) {
+            (Some(client), Some(height)) => get_block_time_at_height(client, height).await,
+            _ => None,
+        };
+        finds_to_import.push(FindToImport {
+            find,
+            earliest_block_time,
+        });
+    }
+    finds_to_import
+}
+
+/// Creates a blank descriptor wallet on the node, or reuses one with the same name, and imports every find into it.
+/// `importdescriptors` returns only after rescanning, so it gets its own long rpc timeout.
+pub async fn send_finds_to_core_wallet(
+    finds_with_earliest_height: Vec<(PathDescriptorFind, Option<u64>)>,
+    master_xpriv: Xpriv,
+    request: CoreWalletRequest,
+    client_setting: ClientSetting,
+) -> Result<CoreWalletImportReport, RetrieverError> {
+    let client = NodeRpcClient::new(client_setting.clone()).await?;
+    let created = match client
+        .call::<Value>("createwallet", request.createwallet_args())
+        .await
+    {
+        Ok(_) => {
+            info!("Wallet {} created on bitcoincore.", request.wallet_name);
+            true
+        }
+        Err(create_error) => {
+            warn!(
+                "Could not create wallet {}, trying to reuse it: {:?}",
+                request.wallet_name, create_error
+            );
+            let _ = client
+                .call::<Value>("loadwallet", vec![Value::from(request.wallet_name.clone())])
+                .await;
+            let loaded_wallets = client.call::<Vec<String>>("listwallets", vec![]).await?;
+            if !loaded_wallets.contains(&request.wallet_name) {
+                return Err(create_error);
+            }
+            false
+        }
+    };
+    let finds_to_import = resolve_finds_to_import(Some(&client), finds_with_earliest_height).await;
+    let requests =
+        build_import_descriptor_requests(&finds_to_import, &master_xpriv, request.variant())?;
+    let wallet_client = NodeRpcClient::new_for_wallet(
+        client_setting_with_timeout(&client_setting, IMPORT_DESCRIPTORS_TIMEOUT_SECONDS),
+        &request.wallet_name,
+    )
+    .await?;
+    let import_arg = bitcoincore_rpc::jsonrpc::serde_json::to_value(&requests)
+        .map_err(|e| RetrieverError::IoError(std::io::Error::other(e)))?;
+    let responses = wallet_client
+        .call::<Vec<Value>>("importdescriptors", vec![import_arg])
+        .await?;
+    let results = requests
+        .iter()
+        .zip(responses.iter())
+        .map(|(request, response)| {
+            DescriptorImportResult::from_response(request.label.clone(), response)
+        })
+        .collect();
+    let balance = wallet_client
+        .call::<Value>("getbalances", vec![])
+        .await
+        .ok()
+        .and_then(|balances| WalletBalance::from_getbalances(&balances));
+    Ok(CoreWalletImportReport {
+        request,
+        created,
+        results,
+        balance,
+    })
+}
+
+async fn get_block_time_at_height(client: &NodeRpcClient, height: u64) -> Option<i64> {
+    let block_hash = client
+        .call::<Value>("getblockhash", vec![Value::from(height)])
+        .await
+        .ok()?;
+    let header = client
+        .call::<Value>("getblockheader", vec![block_hash])
+        .await
+        .ok()?;
+    header["time"].as_i64()
+}
+
+/// Builds the sweep psbt of a plan and saves it to `{data_dir}/exports/`. The previous transactions of legacy
+/// inputs are fetched from the node when it is reachable.
+pub async fn prepare_sweep_psbt(
+    plan: SweepPlan,
+    master_xpriv: Xpriv,
+    data_dir: String,
+    client_setting: Option<ClientSetting>,
+) -> Result<PreparedSweep, RetrieverError> {
+    let txids = plan.txids_needing_previous_transaction();
+    let mut previous_transactions = std::collections::HashMap::new();
+    if !txids.is_empty() {
+        if let Some(client_setting) = client_setting {
+            let client = NodeRpcClient::new(client_setting).await?;
+            for txid in txids.iter() {
+                match client
+                    .call::<String>("getrawtransaction", vec![Value::from(txid.to_string())])
+                    .await
+                    .ok()
+                    .and_then(|raw_tx| hex::decode(raw_tx).ok())
+                    .and_then(|raw_tx| bitcoin::consensus::deserialize::<Transaction>(&raw_tx).ok())
+                {
+                    Some(transaction) => {
+                        previous_transactions.insert(*txid, transaction);
+                    }
+                    None => warn!(
+                        "Previous transaction {} could not be fetched. Is txindex enabled?",
+                        txid
+                    ),
+                }
+            }
+        }
+    }
+    let psbt = plan.to_psbt(&master_xpriv, &previous_transactions)?;
+    let psbt_path = export_file_path(&data_dir, "sweep", "psbt");
+    if let Some(exports_dir) = psbt_path.parent() {
+        std::fs::create_dir_all(exports_dir)?;
+    }
+    // Psbt files hold the binary serialization, as bip174 asks for.
+    std::fs::write(&psbt_path, psbt.serialize())?;
+    info!("Sweep psbt saved to {}.", psbt_path.to_string_lossy());
+    Ok(PreparedSweep {
+        missing_previous_transactions: txids.len() - previous_transactions.len(),
+        plan,
+        psbt,
+        psbt_path,
+    })
+}
+
+/// Signs the sweep with the derived keys and saves the raw transaction to `{data_dir}/exports/`.
+pub async fn sign_sweep(
+    prepared_sweep: PreparedSweep,
+    master_xpriv: Xpriv,
+    data_dir: String,
+) -> Result<SignedSweep, GuiError> {
+    let transaction = prepared_sweep.sign(&master_xpriv)?;
+    let signed_sweep = SignedSweep {
+        transaction,
+        transaction_path: export_file_path(&data_dir, "sweep_signed", "hex"),
+    };
+    write_export_file(&signed_sweep.transaction_path, &signed_sweep.raw_hex())?;
+    info!(
+        "Signed sweep {} saved to {}.",
+        signed_sweep.txid(),
+        signed_sweep.transaction_path.to_string_lossy()
+    );
+    Ok(signed_sweep)
+}
+
+/// Asks the node whether it would accept the signed sweep, without broadcasting it.
+pub async fn test_sweep_mempool_acceptance(
+    raw_hex: String,
+    client_setting: ClientSetting,
+) -> Result<MempoolAcceptance, RetrieverError> {
+    let client = NodeRpcClient::new(client_setting).await?;
+    let response = client
+        .call::<Value>("testmempoolaccept", vec![Value::from(vec![raw_hex])])
+        .await?;
+    MempoolAcceptance::from_testmempoolaccept(&response).ok_or(
+        RetrieverError::ConsensusEncodeError(bitcoin::consensus::encode::Error::ParseFailed(
+            "unexpected testmempoolaccept response",
+        )),
+    )
+}
+
+pub async fn broadcast_sweep(
+    raw_hex: String,
+    client_setting: ClientSetting,
+) -> Result<Txid, RetrieverError> {
+    let client = NodeRpcClient::new(client_setting).await?;
+    let txid = client
+        .call::<Txid>("sendrawtransaction", vec![Value::from(raw_hex)])
+        .await?;
+    info!("Sweep {} broadcast through bitcoincore.", txid);
+    Ok(txid)
+}
+
+/// Looks the sweep up with `getrawtransaction`, which knows mempool transactions and, with `-txindex`, confirmed
+/// ones. Without the index a confirmed sweep is found through its output in the utxo set instead.
+pub async fn get_sweep_broadcast_status(
+    txid: Txid,
+    client_setting: ClientSetting,
+) -> Result<BroadcastStatus, RetrieverError> {
+    let client = NodeRpcClient::new(client_setting).await?;
+    match client
+        .call::<Value>(
+            "getrawtransaction",
+            vec![Value::from(txid.to_string()), Value::from(true)],
+        )
+        .await
+    {
+        Ok(transaction) => {
+            return Ok(match transaction["confirmations"].as_u64() {
+                Some(confirmations) if confirmations > 0 => {
+                    BroadcastStatus::Confirmed { confirmations }
+                }
+                _ => BroadcastStatus::InMempool,
+            })
+        }
+        Err(e) if rpc_error_code(&e) == Some(RPC_INVALID_ADDRESS_OR_KEY) => {}
+        Err(e) => return Err(e),
+    }
+    let confirmed_output = client
+        .call::<Value>(
+            "gettxout",
+            vec![
+                Value::from(txid.to_string()),
+                Value::from(0),
+                Value::from(false),
+            ],
+        )
+        .await?;
+    Ok(match confirmed_output["confirmations"].as_u64() {
+        Some(confirmations) => BroadcastStatus::Confirmed { confirmations },
+        None => BroadcastStatus::NotFound,
+    })
+}
+
+/// Fee rates from `estimatesmartfee` for every priority, floored by the fees of `getmempoolinfo`.
+pub async fn get_fee_estimates(
+    client_setting: ClientSetting,
+) -> Result<FeeEstimates, RetrieverError> {
+    let client = NodeRpcClient::new(client_setting).await?;
+    let mempool_info = client.call::<Value>("getmempoolinfo", vec![]).await?;
+    let min_fee_rate_sat_per_vb = FeeEstimates::min_fee_rate_from_getmempoolinfo(&mempool_info)
+        .ok_or(RetrieverError::ConsensusEncodeError(
+            bitcoin::consensus::encode::Error::ParseFailed("unexpected getmempoolinfo response"),
+        ))?;
+    let mut estimates = vec![];
+    for priority in FeePriority::ALL {
+        let fee_rate = client
+            .call::<Value>(
+                "estimatesmartfee",
+                vec![Value::from(priority.confirmation_target())],
+            )
+            .await?;
+        estimates.push((
+            priority,
+            FeeEstimates::fee_rate_from_estimatesmartfee(&fee_rate),
+        ));
+    }
+    Ok(FeeEstimates {
+        estimates,
+        min_fee_rate_sat_per_vb,
+    })
+}
+
+/// Writes the hashed script pubkeys of every explored path to `{data_dir}/exports/`, for matching on another machine.
+pub async fn derive_scripts_offline(
+    select_descriptors: hashbrown::HashSet<CoveredDescriptors>,
+    explorer: Arc<Explorer>,
+    data_dir: String,
+    network: Network,
+    cancellation_token: CancellationToken,
+) -> Result<OfflineDerivationSummary, RetrieverError> {
+    let secp = Secp256k1::new();
+    let mut writer = DerivedScriptsWriter::create(&data_dir, network)?;
+    let (tx_stream, mut rx_stream) = mpsc::channel(2048);
+    create_derivation_path_stream(explorer.clone(), tx_stream).await;
+    let mut paths_derived = 0u64;
+    let script_types: Vec<CoveredDescriptors> = select_descriptors.into_iter().collect();
+    while let Some(path) = rx_stream.recv().await {
+        if cancellation_token.is_cancelled() {
+            info!("Offline derivation cancelled.");
+            break;
+        }
+        let public_key = PublicKey::new(
+            explorer
+                .get_master_xpriv()
+                .derive_priv(&secp, &path)?
+                .to_keypair(&secp)
+                .public_key(),
+        );
+        for script_type in script_types.iter() {
+            let descriptor = descriptor_for(script_type, public_key)?;
+            writer.write(&DerivedScriptRecord::new(
+                &descriptor.script_pubkey(),
+                path.to_string(),
+                script_type,
+            ))?;
+        }
+        paths_derived += 1;
+    }
+    let (file_path, scripts_written) = writer.finish()?;
+    info!(
+        "{} scripts of {} paths derived to {}.",
+        scripts_written.to_formatted_string(&Locale::en),
+        paths_derived.to_formatted_string(&Locale::en),
+        file_path.to_string_lossy()
+    );
+    Ok(OfflineDerivationSummary {
+        file_path,
+        paths_derived,
+        scripts_written,
+    })
+}
+
+/// Intersects a derived scripts file with the unspent scripts and saves the matches to `{data_dir}/exports/`.
+pub async fn match_derived_scripts_online(
+    derived_scripts_file: PathBuf,
+    uspk_set: Arc<UnspentScriptPubKeysSet>,
+    data_dir: String,
+) -> Result<OnlineMatchReport, RetrieverError> {
+    tokio::task::spawn_blocking(move || {
+        let derived_scripts = DerivedScripts::read(&derived_scripts_file)?;
+        info!(
+            "Matching {} derived scripts against the unspent scripts.",
+            derived_scripts
+                .records_read
+                .to_formatted_string(&Locale::en)
+        );
+        let matches = derived_scripts.match_unspent_scripts(&uspk_set.get_immutable_inner_set());
+        let matches_file = write_online_matches(&matches, &data_dir)?;
+        Ok(OnlineMatchReport {
+            derived_scripts_file,
+            network: derived_scripts.network,
+            scripts_in_file: derived_scripts.records_read,
+            matches,
+            matches_file,
+        })
+    })
+    .await?
+}
+
+fn profiles_dir_or_error() -> Result<PathBuf, RetrieverError> {
+    profiles_dir().ok_or(RetrieverError::IoError(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "no config dir found for the profiles",
+    )))
+}
+
+pub async fn list_settings_profiles() -> Vec<String> {
+    match profiles_dir() {
+        Some(profiles_dir) => tokio::task::spawn_blocking(move || list_profiles(&profiles_dir))
+            .await
+            .unwrap_or_default(),
+        None => vec![],
+    }
+}
+
+pub async fn load_settings_profile(
+    name: String,
+) -> Result<(String, SettingsProfile), RetrieverError> {
+    let profiles_dir = profiles_dir_or_error()?;
+    tokio::task::spawn_blocking(move || {
+        let profile = SettingsProfile::load(&profiles_dir, &name)?;
+        info!("Settings profile {} loaded.", name);
+        Ok((name, profile))
+    })
+    .await?
+}
+
+pub async fn save_settings_profile(
+    name: String,
+    profile: SettingsProfile,
+) -> Result<PathBuf, RetrieverError> {
+    let profiles_dir = profiles_dir_or_error()?;
+    tokio::task::spawn_blocking(move || profile.save(&profiles_dir, &name)).await?
+}
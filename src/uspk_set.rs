@@ -1,4 +1,5 @@
 use std::{
+    path::Path,
     sync::{Arc, Mutex},
     time::Instant,
 };
@@ -8,6 +9,11 @@ use num_format::{Locale, ToFormattedString};
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 
+use crate::domain::{
+    dump_integrity::{DumpReadSummary, TxoutsetHasher},
+    snapshot_coins::SnapshotCoins,
+};
+
 
 #[derive(Debug, Clone)]
 pub struct UnspentScriptPubKeysSet {
@@ -29,19 +35,21 @@ impl UnspentScriptPubKeysSet {
             status: Arc::new(Mutex::new(vec![UspkSetStatus::Empty])),
         }
     }
+    #[allow(clippy::manual_is_multiple_of)]
     pub async fn populate_with_dump_file(
         &mut self,
         dump_file_path: &str,
+        compute_txoutset_hash: bool,
         cancellation_token: CancellationToken,
-    ) -> Result<(), RetrieverError> {
+    ) -> Result<DumpReadSummary, RetrieverError> {
         let creation_start = Instant::now();
         let status = self.status.clone();
         let (set_sender, set_receiver) = tokio::sync::oneshot::channel();
-        let mut dump = txoutset::Dump::new(dump_file_path, txoutset::ComputeAddresses::No)?;
+        let mut dump = SnapshotCoins::open(Path::new(dump_file_path))?;
         // Loop information.
         let step_size = 100000u64;
         let mut average_step_time_in_micros = 0u128;
-        let total_loops = dump.utxo_set_size;
+        let total_loops = dump.coin_count();
         let mut loops_done = 0u64;
         let mut steps_done = 0u128;
         let mut steps_remaining = (total_loops / step_size) as u128;
@@ -50,9 +58,13 @@ impl UnspentScriptPubKeysSet {
         tokio::task::spawn_blocking(move || {
             status.lock().unwrap()[0] = UspkSetStatus::Populating;
             let mut set = hashbrown::HashSet::new();
+            let mut hasher = compute_txoutset_hash.then(TxoutsetHasher::new);
             while !cancellation_token.is_cancelled() {
                 match dump.next() {
                     Some(txout) => {
+                        if let Some(hasher) = hasher.as_mut() {
+                            hasher.add(&txout);
+                        }
                         set.insert(txout.script_pubkey.as_bytes().to_vec());
                         // Loop info stuff.
                         loops_done += 1;
@@ -78,7 +90,12 @@ impl UnspentScriptPubKeysSet {
                         }
                     }
                     None => {
-                        let _ = set_sender.send(set);
+                        let summary = DumpReadSummary {
+                            coins_in_header: total_loops,
+                            coins_read: loops_done,
+                            txoutset_hash: hasher.take().map(|hasher| hasher.finalize()),
+                        };
+                        let _ = set_sender.send((set, summary));
                         status.lock().unwrap()[0] = UspkSetStatus::Ready;
                         break;
                     }
@@ -90,8 +107,9 @@ impl UnspentScriptPubKeysSet {
             total_loops.to_formatted_string(&Locale::en),
             1 + creation_start.elapsed().as_secs() / 60
         );
-        self.set = Arc::new(set_receiver.await.unwrap());
-        Ok(())
+        let (set, summary) = set_receiver.await.unwrap();
+        self.set = Arc::new(set);
+        Ok(summary)
     }
 
     pub fn get_immutable_inner_set(&self) -> Arc<hashbrown::HashSet<Vec<u8>>> {
@@ -0,0 +1,83 @@
+use iced::{
+    mouse,
+    widget::canvas::{self, Canvas, Frame, Geometry, Path},
+    Color, Length, Point, Rectangle, Renderer, Size, Theme,
+};
+use qrcode::QrCode;
+
+use crate::app_message::AppMessage;
+
+/// Modules of empty margin kept around the code so scanners can find its edges.
+const QUIET_ZONE_IN_MODULES: usize = 4;
+pub const QR_CODE_SIZE_IN_PIXELS: u16 = 200;
+
+/// A QR code of an address, drawn module by module on a canvas.
+pub struct AddressQrCode {
+    width_in_modules: usize,
+    dark_modules: Vec<bool>,
+}
+
+impl AddressQrCode {
+    /// Addresses are encoded in upper case when possible, as bech32 then fits the denser alphanumeric mode.
+    pub fn new(address: &str) -> Option<Self> {
+        let data = if address.starts_with("bc1")
+            || address.starts_with("tb1")
+            || address.starts_with("bcrt1")
+        {
+            address.to_uppercase()
+        } else {
+            address.to_string()
+        };
+        let code = QrCode::new(data.as_bytes()).ok()?;
+        Some(AddressQrCode {
+            width_in_modules: code.width(),
+            dark_modules: code
+                .to_colors()
+                .into_iter()
+                .map(|color| color == qrcode::Color::Dark)
+                .collect(),
+        })
+    }
+}
+
+impl canvas::Program<AppMessage> for AddressQrCode {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        frame.fill_rectangle(Point::ORIGIN, bounds.size(), Color::WHITE);
+        let total_modules = self.width_in_modules + 2 * QUIET_ZONE_IN_MODULES;
+        let module_size = bounds.width.min(bounds.height) / total_modules as f32;
+        let dark_modules = Path::new(|builder| {
+            for (index, is_dark) in self.dark_modules.iter().enumerate() {
+                if *is_dark {
+                    let x = index % self.width_in_modules + QUIET_ZONE_IN_MODULES;
+                    let y = index / self.width_in_modules + QUIET_ZONE_IN_MODULES;
+                    builder.rectangle(
+                        Point::new(x as f32 * module_size, y as f32 * module_size),
+                        Size::new(module_size, module_size),
+                    );
+                }
+            }
+        });
+        frame.fill(&dark_modules, Color::BLACK);
+        vec![frame.into_geometry()]
+    }
+}
+
+pub fn address_qr_code_block<'a>(address: &str) -> iced::Element<'a, AppMessage> {
+    match AddressQrCode::new(address) {
+        Some(qr_code) => Canvas::new(qr_code)
+            .width(Length::Fixed(QR_CODE_SIZE_IN_PIXELS as f32))
+            .height(Length::Fixed(QR_CODE_SIZE_IN_PIXELS as f32))
+            .into(),
+        None => iced::widget::text("Address is too long for a QR code.").into(),
+    }
+}
@@ -75,7 +75,7 @@ pub fn bitcoincore_url_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage
         Some(120),
         "url:".to_string(),
         "".to_string(),
-        app.bitcoincore_client_setting_input.get_gui_url(),
+        &app.bitcoincore_client_setting_input.get_gui_url(),
         Box::new(|new_url| {
             AppMessage::SettingInputInGuiChanged(SettingInputInGuiMessage::BitcoincoreUrlChanged(
                 new_url,
@@ -93,7 +93,7 @@ pub fn bitcoincore_rpc_port_block(app: &RetrieverApp) -> iced::Element<'_, AppMe
         Some(60),
         "rpc port:".to_string(),
         "".to_string(),
-        app.bitcoincore_client_setting_input.get_gui_rpc_port(),
+        &app.bitcoincore_client_setting_input.get_gui_rpc_port(),
         Box::new(|new_rpc_port| {
             AppMessage::SettingInputInGuiChanged(
                 SettingInputInGuiMessage::BitcoincoreRpcPortChanged(new_rpc_port),
@@ -111,7 +111,7 @@ pub fn bitcoincore_timeout_block(app: &RetrieverApp) -> iced::Element<'_, AppMes
         Some(60),
         "timeout (seconds):".to_string(),
         "".to_string(),
-        app.bitcoincore_client_setting_input.get_gui_timeout(),
+        &app.bitcoincore_client_setting_input.get_gui_timeout(),
         Box::new(|new_timeout| {
             AppMessage::SettingInputInGuiChanged(
                 SettingInputInGuiMessage::BitcoincoreTimeoutChanged(new_timeout),
@@ -129,7 +129,7 @@ pub fn bitcoincore_cookie_path_block(app: &RetrieverApp) -> iced::Element<'_, Ap
         None,
         "cookie path:".to_string(),
         "".to_string(),
-        app.bitcoincore_client_setting_input.get_gui_cookie_path(),
+        &app.bitcoincore_client_setting_input.get_gui_cookie_path(),
         Box::new(|new_cookie_path| {
             AppMessage::SettingInputInGuiChanged(
                 SettingInputInGuiMessage::BitcoincoreCookiePathChanged(new_cookie_path),
@@ -7,22 +7,23 @@ use crate::{
     app_message::AppMessage, retriever_styles::sanity_check_light::SanityCheckLight, RetrieverApp,
 };
 
-pub fn sanity_checked_text_input(
-    _app: &RetrieverApp,
+#[allow(clippy::too_many_arguments)]
+pub fn sanity_checked_text_input<'a>(
+    _app: &'a RetrieverApp,
     light_height: u16,
     input_width: Option<u16>,
     title: String,
     placeholder: String,
-    value: String,
+    value: &str,
     on_input_callback: Box<dyn Fn(String) -> AppMessage>,
     is_sane: bool,
     is_fixed: bool,
-) -> iced::Element<'_, AppMessage> {
+) -> iced::Element<'a, AppMessage> {
     Row::new()
         .push(Text::new(title))
         .push(Space::new(7, light_height))
         .push(
-            TextInput::new(&placeholder, &value)
+            TextInput::new(&placeholder, value)
                 .width(if let Some(width) = input_width {
                     width.into()
                 } else {
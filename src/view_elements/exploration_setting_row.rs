@@ -8,14 +8,17 @@ use crate::{
         setting_input_fixed::SettingInputFixedMessage,
         setting_input_in_gui::SettingInputInGuiMessage, AppMessage,
     },
+    inputs::secret::SecretString,
     retriever_styles::{
-        fix_button_style::FixButtonStyle, retriever_colors::BITCOIN_ORANGE_COLOR,
+        fix_button_style::FixButtonStyle,
+        retriever_colors::{BITCOIN_ORANGE_COLOR, INSANE_BACKGROUND_COLOR},
         sanity_check_light::SanityCheckLight,
+        stop_button_style::StopButtonStyle,
     },
     RetrieverApp,
 };
 
-use super::common::sanity_checked_text_input;
+use super::{common::sanity_checked_text_input, mnemonic_word_grid::mnemonic_word_grid_block};
 
 pub fn exploration_setting_row(
     app: &RetrieverApp,
@@ -36,6 +39,8 @@ pub fn exploration_setting_row(
         .push(third_row(app))
         .push(Space::new(Length::Fill, 5))
         .push(fourth_row(app))
+        .push(Space::new(Length::Fill, 5))
+        .push(fifth_row(app))
         .padding(15)
         .align_items(iced::Alignment::Start)
         .into()
@@ -75,6 +80,13 @@ pub fn fourth_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .into()
 }
 
+pub fn fifth_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    Row::new()
+        .push(wipe_secrets_block(app))
+        .align_items(Alignment::Center)
+        .into()
+}
+
 pub fn section_title(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
     Column::new()
         .push(
@@ -138,7 +150,7 @@ pub fn base_derivation_paths_block(app: &RetrieverApp) -> iced::Element<'_, AppM
             None,
             "base derivation paths:".to_string(),
             "".to_string(),
-            app.explorer_setting_input.get_gui_base_derivation_paths(),
+            &app.explorer_setting_input.get_gui_base_derivation_paths(),
             Box::new(|base_derivation_paths| {
                 AppMessage::SettingInputInGuiChanged(
                     SettingInputInGuiMessage::BaseDerivationPathsChanged(base_derivation_paths),
@@ -177,7 +189,7 @@ pub fn exploration_path_block(app: &RetrieverApp) -> iced::Element<'_, AppMessag
             None,
             "exploration path:".to_string(),
             "".to_string(),
-            app.explorer_setting_input.get_gui_exploration_path(),
+            &app.explorer_setting_input.get_gui_exploration_path(),
             Box::new(|exploration_path| {
                 AppMessage::SettingInputInGuiChanged(
                     SettingInputInGuiMessage::ExplorationPathChanged(exploration_path),
@@ -198,7 +210,7 @@ pub fn exploration_depth_block(app: &RetrieverApp) -> iced::Element<'_, AppMessa
             Some(60),
             "exploration depth:".to_string(),
             "".to_string(),
-            app.explorer_setting_input.get_gui_exploration_depth(),
+            &app.explorer_setting_input.get_gui_exploration_depth(),
             Box::new(|exploration_depth| {
                 AppMessage::SettingInputInGuiChanged(
                     SettingInputInGuiMessage::ExplorationDepthChanged(exploration_depth),
@@ -248,21 +260,47 @@ pub fn network_selection_block(app: &RetrieverApp) -> iced::Element<'_, AppMessa
 
 pub fn mnemonic_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
     Row::new()
-        .push(sanity_checked_text_input(
-            app,
-            25,
-            None,
-            "mnemonics:".to_string(),
-            "".to_string(),
-            app.explorer_setting_input.get_gui_mnemonic(),
-            Box::new(|mnemonic| {
-                AppMessage::SettingInputInGuiChanged(SettingInputInGuiMessage::MnemonicChanged(
-                    mnemonic,
-                ))
-            }),
-            app.explorer_setting_input.is_gui_mnemonic_sane(),
-            app.explorer_setting_input.is_mnemonic_fixed(),
+        .push(text("mnemonics:"))
+        .push(Space::new(7, 10))
+        .push(match &app.mnemonic_word_grid {
+            Some(mnemonic_word_grid) => mnemonic_word_grid_block(app, mnemonic_word_grid),
+            None => TextInput::new("", app.explorer_setting_input.get_gui_mnemonic())
+                .on_input(|mnemonic| {
+                    AppMessage::SettingInputInGuiChanged(SettingInputInGuiMessage::MnemonicChanged(
+                        SecretString::new(mnemonic),
+                    ))
+                })
+                .secure(!app.is_mnemonic_shown)
+                .width(Length::Fill)
+                .into(),
+        })
+        .push(Space::new(7, 10))
+        .push(
+            Button::new(
+                text(if app.mnemonic_word_grid.is_some() {
+                    "single line"
+                } else {
+                    "word grid"
+                })
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .width(100)
+            .on_press(AppMessage::ToggleMnemonicWordGrid),
+        )
+        .push(Space::new(7, 10))
+        .push(secret_visibility_button(
+            app.is_mnemonic_shown,
+            AppMessage::ToggleMnemonicVisibility,
         ))
+        .push(Space::new(1, 10))
+        .push(
+            Button::new("")
+                .height(25)
+                .style(iced::theme::Button::Custom(Box::new(SanityCheckLight {
+                    is_sane: app.explorer_setting_input.is_gui_mnemonic_sane(),
+                    is_fixed: app.explorer_setting_input.is_mnemonic_fixed(),
+                }))),
+        )
         // .push(text("mnemonic:"))
         // .push(Space::new(21, 10))
         // .push(
@@ -292,14 +330,20 @@ pub fn passphrase_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .push(text("passphrase:"))
         .push(Space::new(7, 10))
         .push(
-            TextInput::new("", &app.explorer_setting_input.get_gui_passphrase())
+            TextInput::new("", app.explorer_setting_input.get_gui_passphrase())
                 .on_input(|passphrase| {
                     AppMessage::SettingInputInGuiChanged(
-                        SettingInputInGuiMessage::PassphraseChanged(passphrase),
+                        SettingInputInGuiMessage::PassphraseChanged(SecretString::new(passphrase)),
                     )
                 })
+                .secure(!app.is_passphrase_shown)
                 .width(Length::Fill),
         )
+        .push(Space::new(7, 10))
+        .push(secret_visibility_button(
+            app.is_passphrase_shown,
+            AppMessage::TogglePassphraseVisibility,
+        ))
         .push(Space::new(1, 10))
         .push(
             Button::new("")
@@ -312,3 +356,56 @@ pub fn passphrase_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .align_items(Alignment::Center)
         .into()
 }
+
+/// Toggles a masked secret input between dots and plain text.
+pub fn secret_visibility_button<'a>(
+    is_shown: bool,
+    on_press: AppMessage,
+) -> iced::Element<'a, AppMessage> {
+    Button::new(
+        text(if is_shown { "hide" } else { "show" })
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(60)
+    .on_press(on_press)
+    .into()
+}
+
+pub fn wipe_secrets_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let is_idle_input_sane = app.wipe_secrets_after_idle_input.trim().is_empty()
+        || app.wipe_secrets_after_idle().is_some();
+    let mut wipe_button = Button::new(
+        text("wipe secrets")
+            .horizontal_alignment(iced::alignment::Horizontal::Center)
+            .vertical_alignment(iced::alignment::Vertical::Center),
+    )
+    .width(150)
+    .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)));
+    if app.has_secrets() {
+        wipe_button = wipe_button.on_press(AppMessage::WipeSecrets);
+    }
+    Row::new()
+        .push(wipe_button)
+        .push(Space::new(15, 10))
+        .push(text("wipe after search:"))
+        .push(Space::new(7, 10))
+        .push(
+            Checkbox::new("", app.wipe_secrets_after_search)
+                .on_toggle(AppMessage::WipeSecretsAfterSearchChanged),
+        )
+        .push(Space::new(15, 10))
+        .push(if is_idle_input_sane {
+            text("wipe after idle (minutes):")
+        } else {
+            text("wipe after idle (minutes):")
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+        })
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("off", &app.wipe_secrets_after_idle_input)
+                .on_input(AppMessage::WipeSecretsAfterIdleChanged)
+                .width(60),
+        )
+        .align_items(Alignment::Center)
+        .into()
+}
@@ -1,24 +1,74 @@
 use std::fmt::{Debug, Display};
 
-use bitceptron_retriever::path_pairs::PathScanResultDescriptorTrio;
-use bitcoin::{bip32::DerivationPath, secp256k1::PublicKey};
+use bitceptron_retriever::covered_descriptors::CoveredDescriptors;
+use bitcoin::{bip32::DerivationPath, secp256k1::PublicKey, Address, Network};
 use miniscript::Descriptor;
 use num_format::{Locale, ToFormattedString};
 
+use crate::domain::finds::{script_type_of, DetailedFind, FoundUtxo};
+
 pub struct FinalFinds {
     result_num: u64,
     path: DerivationPath,
     amount_in_sat: u64,
     descriptor: Descriptor<PublicKey>,
+    unspents: Vec<FoundUtxo>,
+    at_height: Option<u64>,
+    script_type: Option<CoveredDescriptors>,
+    /// `None` for p2pk, which has no address.
+    address: Option<Address>,
 }
 
 impl FinalFinds {
-    pub fn new(result_num: u64, details: &PathScanResultDescriptorTrio) -> Self {
+    pub fn new(result_num: u64, details: &DetailedFind, network: Network) -> Self {
         FinalFinds {
             result_num,
-            path: details.0.clone(),
-            amount_in_sat: details.1.total_amount.to_sat(),
-            descriptor: details.2.clone(),
+            path: details.path.clone(),
+            amount_in_sat: details.total_amount_in_sat(),
+            descriptor: details.descriptor.clone(),
+            unspents: details.unspents.clone(),
+            at_height: details.at_height,
+            script_type: script_type_of(&details.descriptor),
+            address: details.descriptor.address(network).ok(),
+        }
+    }
+
+    pub fn get_result_num(&self) -> u64 {
+        self.result_num
+    }
+
+    pub fn get_path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    pub fn get_amount_in_sat(&self) -> u64 {
+        self.amount_in_sat
+    }
+
+    pub fn get_descriptor(&self) -> &Descriptor<PublicKey> {
+        &self.descriptor
+    }
+
+    pub fn get_unspents(&self) -> &Vec<FoundUtxo> {
+        &self.unspents
+    }
+
+    pub fn get_at_height(&self) -> Option<u64> {
+        self.at_height
+    }
+
+    pub fn get_script_type(&self) -> Option<CoveredDescriptors> {
+        self.script_type.clone()
+    }
+
+    pub fn get_address(&self) -> Option<&Address> {
+        self.address.as_ref()
+    }
+
+    pub fn get_address_string(&self) -> String {
+        match &self.address {
+            Some(address) => address.to_string(),
+            None => "no address".to_string(),
         }
     }
 }
@@ -27,9 +77,19 @@ impl Display for FinalFinds {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "\nResult {}\nPath: {}\nAmount(satoshis): {}\nDescriptor: {}\n",
-            self.result_num, self.path, self.amount_in_sat.to_formatted_string(&Locale::en), self.descriptor
-        )
+            "\nResult {}\nPath: {}\nAddress: {}\nAmount(satoshis): {}\nDescriptor: {}\n",
+            self.result_num, self.path, self.get_address_string(), self.amount_in_sat.to_formatted_string(&Locale::en), self.descriptor
+        )?;
+        for utxo in self.unspents.iter() {
+            writeln!(
+                f,
+                "  {} | {} sats | height {}",
+                utxo.out_point,
+                utxo.amount_in_sat.to_formatted_string(&Locale::en),
+                utxo.height.to_formatted_string(&Locale::en)
+            )?;
+        }
+        Ok(())
     }
 }
 
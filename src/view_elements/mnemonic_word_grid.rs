@@ -0,0 +1,98 @@
+use iced::{
+    widget::{text, text_input, Button, Column, PickList, Row, Space, TextInput},
+    Alignment, Length,
+};
+
+use crate::{
+    app_message::AppMessage,
+    domain::mnemonic_entry::{ChecksumState, MnemonicWordGrid, WordState, MNEMONIC_WORD_COUNTS},
+    inputs::secret::SecretString,
+    retriever_styles::retriever_colors::INSANE_BACKGROUND_COLOR,
+    RetrieverApp,
+};
+
+const WORDS_PER_ROW: usize = 6;
+
+pub fn mnemonic_word_input_id(index: usize) -> text_input::Id {
+    text_input::Id::new(format!("mnemonic_word_{}", index))
+}
+
+/// One input per word. Enter completes the word from the BIP39 list and moves to the next one.
+pub fn mnemonic_word_grid_block<'a>(
+    app: &'a RetrieverApp,
+    grid: &'a MnemonicWordGrid,
+) -> iced::Element<'a, AppMessage> {
+    let checksum_state = grid.checksum_state();
+    let mut column = Column::new().push(
+        Row::new()
+            .push(text("words:"))
+            .push(Space::new(7, 10))
+            .push(PickList::new(
+                MNEMONIC_WORD_COUNTS,
+                Some(grid.word_count()),
+                AppMessage::MnemonicWordCountChanged,
+            ))
+            .push(Space::new(15, 10))
+            .push(if checksum_state == ChecksumState::Invalid {
+                text(checksum_state.to_string())
+                    .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+            } else {
+                text(checksum_state.to_string())
+            })
+            .align_items(Alignment::Center),
+    );
+    for row_start in (0..grid.word_count()).step_by(WORDS_PER_ROW) {
+        let mut row = Row::new().align_items(Alignment::Center);
+        for index in row_start..(row_start + WORDS_PER_ROW).min(grid.word_count()) {
+            let position = text(format!("{:>2}.", index + 1)).width(25);
+            row = row
+                .push(if grid.word_state(index) == WordState::Invalid {
+                    position.style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+                } else {
+                    position
+                })
+                .push(
+                    TextInput::new("", grid.word(index))
+                        .id(mnemonic_word_input_id(index))
+                        .on_input(move |word| {
+                            AppMessage::MnemonicWordChanged(index, SecretString::new(word))
+                        })
+                        .on_submit(AppMessage::MnemonicWordSubmitted(index))
+                        .secure(!app.is_mnemonic_shown)
+                        .width(Length::Fill),
+                )
+                .push(Space::new(10, 10));
+        }
+        column = column.push(Space::new(Length::Fill, 3)).push(row);
+    }
+    let invalid_word_positions = grid.invalid_word_positions();
+    if !invalid_word_positions.is_empty() {
+        column = column.push(
+            text(format!(
+                "not in the BIP39 word list: word {}",
+                invalid_word_positions
+                    .iter()
+                    .map(|position| position.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+            .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+        );
+    }
+    // Suggestions would show the typed word, so they follow the show/hide toggle.
+    if let Some(index) = grid.active_word.filter(|_| app.is_mnemonic_shown) {
+        if grid.word_state(index) == WordState::Partial {
+            let mut suggestions = Row::new()
+                .push(text(format!("word {}:", index + 1)))
+                .align_items(Alignment::Center);
+            for suggestion in grid.suggestions(index) {
+                suggestions = suggestions.push(Space::new(7, 10)).push(
+                    Button::new(text(suggestion))
+                        .on_press(AppMessage::MnemonicSuggestionChosen(index, suggestion)),
+                );
+            }
+            column = column.push(Space::new(Length::Fill, 3)).push(suggestions);
+        }
+    }
+    column.width(Length::Fill).into()
+}
@@ -7,5 +7,8 @@ pub mod results_row;
 pub use bitcoincore_client_setting_row::bitcoincore_client_setting_row;
 pub use exploration_setting_row::exploration_setting_row;
 pub use retriever_setting_row::retriever_setting_row;
+pub mod address_qr_code;
 pub mod common;
-pub mod final_finds;
\ No newline at end of file
+pub mod final_finds;
+pub mod mnemonic_word_grid;
+pub mod profiles_row;
\ No newline at end of file
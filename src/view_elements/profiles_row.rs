@@ -0,0 +1,72 @@
+use iced::{
+    widget::{text, Button, PickList, Row, Space, Text, TextInput},
+    Alignment, Length,
+};
+
+use crate::{
+    app_message::AppMessage, domain::profiles::is_valid_profile_name,
+    retriever_styles::retriever_colors::INSANE_BACKGROUND_COLOR, RetrieverApp,
+};
+
+/// Saves and loads the non-secret settings of all sections. The mnemonic and passphrase are never saved.
+pub fn profiles_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let mut load_button =
+        Button::new(text("load").horizontal_alignment(iced::alignment::Horizontal::Center))
+            .width(80);
+    if app.selected_profile.is_some() {
+        load_button = load_button.on_press(AppMessage::LoadProfile);
+    }
+    let is_profile_name_valid = is_valid_profile_name(app.profile_name_input.trim());
+    let mut save_button =
+        Button::new(text("save").horizontal_alignment(iced::alignment::Horizontal::Center))
+            .width(80);
+    if is_profile_name_valid {
+        save_button = save_button.on_press(AppMessage::SaveProfile);
+    }
+    let status = match (&app.loaded_profile, &app.last_saved_profile) {
+        (Some(profile_name), _) => Text::new(format!(
+            "Profile {} loaded. Fix each section to use it.",
+            profile_name
+        )),
+        (None, Some(profile_path)) => Text::new(format!(
+            "Saved to {}, without the mnemonic and passphrase.",
+            profile_path.display()
+        )),
+        (None, None) if !app.profile_name_input.trim().is_empty() && !is_profile_name_valid => {
+            Text::new("Profile names may only hold letters, digits, - and _.")
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+        }
+        (None, None) => Text::new(""),
+    };
+    Row::new()
+        .push(text("profile:"))
+        .push(Space::new(7, 10))
+        .push(
+            PickList::new(
+                app.profile_names.clone(),
+                app.selected_profile.clone(),
+                AppMessage::ProfileSelected,
+            )
+            .placeholder("no saved profiles")
+            .width(200),
+        )
+        .push(Space::new(7, 10))
+        .push(load_button)
+        .push(Space::new(30, 10))
+        .push(text("save as:"))
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("profile name", &app.profile_name_input)
+                .on_input(AppMessage::ProfileNameChanged)
+                .on_submit(AppMessage::SaveProfile)
+                .width(200),
+        )
+        .push(Space::new(7, 10))
+        .push(save_button)
+        .push(Space::new(15, 10))
+        .push(status)
+        .push(Space::new(Length::Fill, 10))
+        .padding(15)
+        .align_items(Alignment::Center)
+        .into()
+}
@@ -1,12 +1,26 @@
 use iced::{
     advanced::widget::Text,
-    widget::{Column, Container, Scrollable, Space},
-    Font, Length,
+    widget::{text, Button, Checkbox, Column, Container, PickList, Row, Rule, Scrollable, Space, TextInput},
+    Alignment, Font, Length,
 };
+use num_format::{Locale, ToFormattedString};
 
 use crate::{
     app_message::AppMessage,
-    retriever_styles::{outputs_style::OutputStyles, retriever_colors::BITCOIN_ORANGE_COLOR},
+    domain::{
+        core_wallet::is_wallet_name_sane,
+        descriptor_export::{ImportDescriptorsVariant, SpendableDescriptorsDestination},
+        fee_estimates::FeePriority,
+        results_export::ExportFormat,
+        results_table::{script_type_name, ResultsSortColumn, ScriptTypeFilter},
+        wif_export::{electrum_script_type_prefix, WIF_EXPORT_CONFIRMATION_PHRASE},
+    },
+    retriever_styles::{
+        outputs_style::OutputStyles,
+        retriever_colors::{BITCOIN_ORANGE_COLOR, INSANE_BACKGROUND_COLOR},
+        stop_button_style::StopButtonStyle,
+    },
+    view_elements::{address_qr_code::address_qr_code_block, final_finds::FinalFinds},
     RetrieverApp,
 };
 
@@ -29,9 +43,24 @@ pub fn results_row(
 
 pub fn first_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
     Column::new()
-        .push(results_title_block(app))
+        .push(
+            Row::new()
+                .push(results_title_block(app))
+                .push(export_block(app))
+                .align_items(Alignment::Center),
+        )
         .push(results_block(app))
         .push(Space::new(15, 10))
+        .push(spendable_descriptors_warning_block(app))
+        .push(wif_keys_warning_block(app))
+        .push(wif_keys_block(app))
+        .push(core_wallet_block(app))
+        .push(Space::new(15, 10))
+        .push(sweep_block(app))
+        .push(Space::new(15, 10))
+        .push(message_signing_block(app))
+        .push(message_verification_block(app))
+        .push(Space::new(15, 10))
         .push(errors_title_block(app))
         .push(errors_block(app))
         .push(Space::new(15, 10))
@@ -39,6 +68,694 @@ pub fn first_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .into()
 }
 
+pub fn export_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let can_export = !app.finds.is_empty() && app.retriever_specific_setting_input.is_input_fixed();
+    let mut row = Row::new();
+    if let Some(last_export) = &app.last_export {
+        row = row
+            .push(Text::new(format!("exported to {}", last_export.to_string_lossy())))
+            .push(Space::new(10, 10));
+    }
+    for format in [ExportFormat::Csv, ExportFormat::Json] {
+        let mut export_button = Button::new(
+            text(format!("export {}", format))
+                .vertical_alignment(iced::alignment::Vertical::Center)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+        )
+        .width(120);
+        if can_export {
+            export_button = export_button.on_press(AppMessage::ExportResults(format));
+        }
+        row = row.push(export_button).push(Space::new(5, 10));
+    }
+    for variant in [ImportDescriptorsVariant::WatchOnly, ImportDescriptorsVariant::Spendable] {
+        let mut export_button = Button::new(
+            text(format!("{} descriptors", variant))
+                .vertical_alignment(iced::alignment::Vertical::Center)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+        )
+        .width(190);
+        if can_export && app.pending_spendable_descriptors.is_none() {
+            export_button = export_button.on_press(AppMessage::ExportImportDescriptors(variant));
+            if variant == ImportDescriptorsVariant::Spendable {
+                export_button = export_button.style(iced::theme::Button::Custom(Box::new(StopButtonStyle)));
+            }
+        }
+        row = row.push(export_button).push(Space::new(5, 10));
+    }
+    let mut wif_keys_button = Button::new(
+        text("WIF keys")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(120);
+    if !app.finds.is_empty() && !app.is_wif_keys_pending && app.wif_keys.is_empty() {
+        wif_keys_button = wif_keys_button
+            .on_press(AppMessage::ShowWifKeys)
+            .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)));
+    }
+    row.push(wif_keys_button).align_items(Alignment::Center).into()
+}
+
+pub fn core_wallet_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    if app.finds.is_empty() {
+        return Space::new(0, 0).into();
+    }
+    let is_wallet_name_sane = is_wallet_name_sane(app.core_wallet_name.trim());
+    let mut send_button = Button::new(
+        text("send to bitcoincore wallet")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(230);
+    if is_wallet_name_sane
+        && app.bitcoincore_client_setting_input.is_input_fixed()
+        && !app.is_core_wallet_import_in_progress
+        && app.pending_spendable_descriptors.is_none()
+    {
+        send_button = send_button.on_press(AppMessage::SendFindsToCoreWallet);
+    }
+    let controls = Row::new()
+        .push(if is_wallet_name_sane || app.core_wallet_name.is_empty() {
+            text("bitcoincore wallet name:")
+        } else {
+            text("bitcoincore wallet name:").style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+        })
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("retriever_recovery", &app.core_wallet_name)
+                .on_input(AppMessage::CoreWalletNameChanged)
+                .width(250),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Checkbox::new("import private keys", app.core_wallet_spendable)
+                .on_toggle(AppMessage::CoreWalletSpendableChanged),
+        )
+        .push(Space::new(15, 10))
+        .push(send_button)
+        .align_items(Alignment::Center);
+    let mut column = Column::new().push(controls);
+    if app.core_wallet_spendable {
+        column = column.push(
+            Text::new("The wallet will hold the master xprv of your mnemonic. Make sure the node is yours and its wallet files are safe.")
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+        );
+    }
+    if app.is_core_wallet_import_in_progress {
+        column = column.push(Text::new("Importing into bitcoincore. The node rescans from the earliest found block, which may take a while."));
+    }
+    if let Some(report) = &app.core_wallet_import_report {
+        column = column.push(Text::new(format!(
+            "{} wallet {}: {} of {} descriptors imported.",
+            if report.created { "Created" } else { "Reused" },
+            report.request.wallet_name,
+            report.imported_count(),
+            report.results.len()
+        )));
+        for result in report.results.iter() {
+            column = column.push(if result.success {
+                Text::new(result.to_string())
+            } else {
+                Text::new(result.to_string()).style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+            });
+        }
+        column = column.push(Text::new(match &report.balance {
+            Some(balance) => format!("wallet balance | {}", balance),
+            None => "wallet balance could not be fetched.".to_string(),
+        }));
+    }
+    column.into()
+}
+
+pub fn sweep_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    if app.final_finds.is_empty() {
+        return Space::new(0, 0).into();
+    }
+    let plan = app.sweep_plan();
+    let mut build_button = Button::new(
+        text("build sweep psbt")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(180);
+    if plan.is_ok()
+        && app.retriever_specific_setting_input.is_input_fixed()
+        && !app.is_sweep_in_progress
+    {
+        build_button = build_button.on_press(AppMessage::BuildSweepPsbt);
+    }
+    let controls = Row::new()
+        .push(text("sweep to:"))
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("destination address", &app.sweep_destination_input)
+                .on_input(AppMessage::SweepDestinationChanged)
+                .width(450),
+        )
+        .push(Space::new(15, 10))
+        .push(text("fee rate (sat/vB):"))
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("2", &app.sweep_fee_rate_input)
+                .on_input(AppMessage::SweepFeeRateChanged)
+                .width(80),
+        )
+        .push(Space::new(15, 10))
+        .push(build_button)
+        .align_items(Alignment::Center);
+    let mut column = Column::new()
+        .push(controls)
+        .push(fee_estimates_block(app));
+    column = column.push(match &plan {
+        Ok(plan) => Text::new(format!(
+            "{} unspents | ~{} vB | total: {} sats | fee: {} sats | after fee: {} sats",
+            plan.inputs.len(),
+            plan.estimated_vsize.to_formatted_string(&Locale::en),
+            plan.total_in_sat.to_formatted_string(&Locale::en),
+            plan.fee_in_sat.to_formatted_string(&Locale::en),
+            plan.amount_after_fee_in_sat.to_formatted_string(&Locale::en),
+        )),
+        Err(_) if app.sweep_destination_input.is_empty() || app.sweep_fee_rate_input.is_empty() => {
+            Text::new("Enter a destination address and a fee rate to sweep every found unspent.")
+        }
+        Err(problem) => Text::new(problem.to_string()).style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+    });
+    if app.is_sweep_in_progress {
+        column = column.push(Text::new("Building the sweep psbt."));
+    }
+    if let Some(prepared_sweep) = &app.prepared_sweep {
+        let psbt_base64 = prepared_sweep.psbt.to_string();
+        column = column
+            .push(Text::new(format!(
+                "psbt saved to {}",
+                prepared_sweep.psbt_path.to_string_lossy()
+            )))
+            .push(
+                Row::new()
+                    .push(Text::new("base64 psbt:"))
+                    .push(Space::new(10, 10))
+                    .push(
+                        Button::new(text("copy"))
+                            .on_press(AppMessage::CopyToClipboard(psbt_base64))
+                            .padding([2, 10]),
+                    )
+                    .push(Space::new(15, 10))
+                    .push({
+                        let mut sign_button = Button::new(text("sign locally")).padding([2, 10]);
+                        if !app.is_sweep_in_progress {
+                            sign_button = sign_button.on_press(AppMessage::SignSweep);
+                        }
+                        sign_button
+                    })
+                    .align_items(Alignment::Center),
+            );
+        if prepared_sweep.missing_previous_transactions > 0 {
+            column = column.push(
+                Text::new(format!(
+                    "{} previous transactions of legacy inputs could not be fetched from the node. Some signers refuse such inputs.",
+                    prepared_sweep.missing_previous_transactions
+                ))
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            );
+        }
+    }
+    if let Some(signed_sweep) = &app.signed_sweep {
+        let raw_hex = signed_sweep.raw_hex();
+        column = column
+            .push(Text::new(format!("signed txid: {}", signed_sweep.txid())))
+            .push(Text::new(format!(
+                "signed transaction saved to {}",
+                signed_sweep.transaction_path.to_string_lossy()
+            )))
+            .push(
+                Row::new()
+                    .push(Text::new("raw transaction:"))
+                    .push(Space::new(10, 10))
+                    .push(
+                        Button::new(text("copy"))
+                            .on_press(AppMessage::CopyToClipboard(raw_hex.clone()))
+                            .padding([2, 10]),
+                    )
+                    .align_items(Alignment::Center),
+            )
+            .push(
+                Container::new(Scrollable::new(Text::new(raw_hex)).width(Length::Fill))
+                    .width(Length::Fill)
+                    .max_height(120)
+                    .style(iced::theme::Container::Custom(Box::new(OutputStyles))),
+            )
+            .push(Space::new(15, 10))
+            .push(sweep_broadcast_block(app))
+            .push(sweep_broadcast_warning_block(app));
+    }
+    column.into()
+}
+
+/// The fee rates suggested by the node, each setting the sweep fee rate when pressed.
+pub fn fee_estimates_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let mut estimates_button = Button::new(text("get fee estimates")).padding([2, 10]);
+    if app.bitcoincore_client_setting_input.is_input_fixed()
+        && !app.is_getting_fee_estimates_in_progress
+    {
+        estimates_button = estimates_button.on_press(AppMessage::GetFeeEstimates);
+    }
+    let mut row = Row::new().push(estimates_button);
+    if let Some(fee_estimates) = &app.fee_estimates {
+        for priority in FeePriority::ALL {
+            row = row.push(Space::new(10, 10));
+            let Some(fee_rate) = fee_estimates.fee_rate_for(priority) else {
+                row = row.push(Text::new(format!("{}: no estimate", priority)));
+                continue;
+            };
+            let fee_rate_input = format!("{:.1}", (fee_rate * 10.).ceil() / 10.);
+            let label = match app.sweep_plan_at(&fee_rate_input) {
+                Ok(plan) => format!(
+                    "{}: {} sat/vB ({} sats)",
+                    priority,
+                    fee_rate_input,
+                    plan.fee_in_sat.to_formatted_string(&Locale::en)
+                ),
+                Err(_) => format!("{}: {} sat/vB", priority, fee_rate_input),
+            };
+            row = row.push(
+                Button::new(text(label))
+                    .on_press(AppMessage::SweepFeeRateChanged(fee_rate_input))
+                    .padding([2, 10]),
+            );
+        }
+        row = row.push(Space::new(10, 10)).push(Text::new(format!(
+            "node minimum: {:.2} sat/vB",
+            fee_estimates.min_fee_rate_sat_per_vb
+        )));
+    }
+    row.align_items(Alignment::Center).into()
+}
+
+pub fn message_signing_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let Some(final_find) = app
+        .final_finds
+        .iter()
+        .find(|final_find| Some(final_find.get_result_num()) == app.message_signing_result_num)
+    else {
+        return Space::new(0, 0).into();
+    };
+    let controls = Row::new()
+        .push(text(format!("message to sign with {}:", final_find.get_address_string())))
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("message", &app.message_to_sign_input)
+                .on_input(AppMessage::MessageToSignChanged)
+                .width(Length::Fill),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Button::new(text("sign"))
+                .on_press(AppMessage::SignMessage)
+                .padding([2, 10]),
+        )
+        .push(Space::new(5, 10))
+        .push(
+            Button::new(text("close"))
+                .on_press(AppMessage::CloseMessageSigning)
+                .padding([2, 10]),
+        )
+        .align_items(Alignment::Center);
+    let mut column = Column::new().push(controls);
+    match &app.signed_message {
+        Some(Ok(signed_message)) => {
+            column = column
+                .push(Text::new(format!(
+                    "{} signature of \"{}\" by {}:",
+                    signed_message.format, signed_message.message, signed_message.address
+                )))
+                .push(
+                    Row::new()
+                        .push(Text::new(signed_message.signature.as_str()).font(Font::MONOSPACE))
+                        .push(Space::new(10, 10))
+                        .push(
+                            Button::new(text("copy"))
+                                .on_press(AppMessage::CopyToClipboard(
+                                    signed_message.signature.clone(),
+                                ))
+                                .padding([2, 10]),
+                        )
+                        .align_items(Alignment::Center),
+                );
+        }
+        Some(Err(problem)) => {
+            column = column.push(
+                Text::new(problem.to_string()).style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            );
+        }
+        None => {}
+    }
+    column.into()
+}
+
+/// Checks a pasted legacy or bip322 signature against an address and message.
+pub fn message_verification_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let inputs = Row::new()
+        .push(text("verify a signed message:"))
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("address", &app.verify_address_input)
+                .on_input(AppMessage::VerifyAddressChanged)
+                .width(Length::FillPortion(3)),
+        )
+        .push(Space::new(5, 10))
+        .push(
+            TextInput::new("message", &app.verify_message_input)
+                .on_input(AppMessage::VerifyMessageChanged)
+                .width(Length::FillPortion(3)),
+        )
+        .push(Space::new(5, 10))
+        .push(
+            TextInput::new("base64 signature", &app.verify_signature_input)
+                .on_input(AppMessage::VerifySignatureChanged)
+                .width(Length::FillPortion(4)),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Button::new(text("verify"))
+                .on_press(AppMessage::VerifyMessageSignature)
+                .padding([2, 10]),
+        )
+        .align_items(Alignment::Center);
+    let mut column = Column::new().push(inputs);
+    match &app.message_verification {
+        Some(Ok(format)) => {
+            column = column.push(Text::new(format!("Valid {} signature.", format)));
+        }
+        Some(Err(problem)) => {
+            column = column.push(
+                Text::new(problem.to_string()).style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            );
+        }
+        None => {}
+    }
+    column.into()
+}
+
+pub fn sweep_broadcast_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let can_reach_node =
+        app.bitcoincore_client_setting_input.is_input_fixed() && !app.is_sweep_in_progress;
+    let is_accepted = app
+        .sweep_mempool_acceptance
+        .as_ref()
+        .is_some_and(|acceptance| acceptance.allowed);
+    let is_broadcast = app.sweep_broadcast.as_ref().is_some_and(|sweep_broadcast| {
+        app.signed_sweep
+            .as_ref()
+            .is_some_and(|signed_sweep| signed_sweep.txid() == sweep_broadcast.txid)
+    });
+    let mut test_button = Button::new(
+        text("test with node")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(180);
+    if can_reach_node && !is_broadcast {
+        test_button = test_button.on_press(AppMessage::TestSweepMempoolAcceptance);
+    }
+    let mut broadcast_button = Button::new(
+        text("broadcast")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(180);
+    if can_reach_node && is_accepted && !is_broadcast && !app.is_sweep_broadcast_pending {
+        broadcast_button = broadcast_button
+            .on_press(AppMessage::BroadcastSweep)
+            .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)));
+    }
+    let mut column = Column::new().push(
+        Row::new()
+            .push(test_button)
+            .push(Space::new(15, 10))
+            .push(broadcast_button)
+            .align_items(Alignment::Center),
+    );
+    if let Some(acceptance) = &app.sweep_mempool_acceptance {
+        column = column.push(if acceptance.allowed {
+            Text::new(acceptance.to_string())
+        } else {
+            Text::new(acceptance.to_string()).style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+        });
+    }
+    if let Some(sweep_broadcast) = &app.sweep_broadcast {
+        column = column.push(Text::new(format!(
+            "broadcast {}: {}",
+            sweep_broadcast.txid, sweep_broadcast.status
+        )));
+    }
+    column.into()
+}
+
+pub fn sweep_broadcast_warning_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let (true, Some(plan)) = (
+        app.is_sweep_broadcast_pending,
+        app.prepared_sweep.as_ref().map(|prepared_sweep| &prepared_sweep.plan),
+    ) else {
+        return Space::new(0, 0).into();
+    };
+    let buttons = Row::new()
+        .push(Space::new(Length::Fill, 10))
+        .push(
+            Button::new(
+                text("cancel")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::SweepBroadcastDismissed)
+            .height(30)
+            .width(150),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Button::new(
+                text("send it")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::SweepBroadcastConfirmed)
+            .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)))
+            .height(30)
+            .width(200),
+        )
+        .align_items(iced::Alignment::Center);
+    Container::new(
+        Column::new()
+            .push(
+                Text::new(format!(
+                    "This sends {} sats to {} and pays {} sats in fees. A broadcast transaction cannot be called back.",
+                    plan.amount_after_fee_in_sat.to_formatted_string(&Locale::en),
+                    plan.destination,
+                    plan.fee_in_sat.to_formatted_string(&Locale::en),
+                ))
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            )
+            .push(Text::new("Check the destination address once more before sending."))
+            .push(Space::new(Length::Fill, 10))
+            .push(buttons),
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(iced::theme::Container::Custom(Box::new(OutputStyles)))
+    .into()
+}
+
+pub fn spendable_descriptors_warning_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let Some(destination) = app.pending_spendable_descriptors else {
+        return Space::new(0, 0).into();
+    };
+    let buttons = Row::new()
+        .push(Space::new(Length::Fill, 10))
+        .push(
+            Button::new(
+                text("cancel")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::SpendableDescriptorsExportDismissed)
+            .height(30)
+            .width(150),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Button::new(
+                text(match destination {
+                    SpendableDescriptorsDestination::ExportFile => "export private keys",
+                    SpendableDescriptorsDestination::CoreWallet => "send private keys",
+                })
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::SpendableDescriptorsExportConfirmed)
+            .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)))
+            .height(30)
+            .width(200),
+        )
+        .align_items(iced::Alignment::Center);
+    Container::new(
+        Column::new()
+            .push(
+                Text::new(match destination {
+                    SpendableDescriptorsDestination::ExportFile => "The spendable export holds your master private key (xprv) in plain text. Anyone who reads the file can take every coin derivable from your mnemonic and passphrase, not only the ones found here.",
+                    SpendableDescriptorsDestination::CoreWallet => "Importing private keys sends your master private key (xprv) to bitcoincore over rpc and stores it in the wallet. Anyone with access to the node or its wallet files can take every coin derivable from your mnemonic and passphrase, not only the ones found here.",
+                })
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            )
+            .push(Text::new(match destination {
+                SpendableDescriptorsDestination::ExportFile => "Only export it on an offline machine or straight into your own bitcoincore, and delete the file right after importing it. If in doubt, use the watch-only export.",
+                SpendableDescriptorsDestination::CoreWallet => "Only send it to a node you own, over a connection nobody else can read. If in doubt, uncheck import private keys.",
+            }))
+            .push(Space::new(Length::Fill, 10))
+            .push(buttons),
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(iced::theme::Container::Custom(Box::new(OutputStyles)))
+    .into()
+}
+
+pub fn wif_keys_warning_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    if !app.is_wif_keys_pending {
+        return Space::new(0, 0).into();
+    }
+    let is_confirmed = app.wif_keys_confirmation_input.trim() == WIF_EXPORT_CONFIRMATION_PHRASE;
+    let mut show_button = Button::new(
+        text("show private keys")
+            .font(Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .height(30)
+    .width(200);
+    if is_confirmed {
+        show_button = show_button
+            .on_press(AppMessage::WifKeysConfirmed)
+            .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)));
+    }
+    let buttons = Row::new()
+        .push(
+            TextInput::new(WIF_EXPORT_CONFIRMATION_PHRASE, &app.wif_keys_confirmation_input)
+                .on_input(AppMessage::WifKeysConfirmationChanged)
+                .width(300),
+        )
+        .push(Space::new(Length::Fill, 10))
+        .push(
+            Button::new(
+                text("cancel")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::WifKeysDismissed)
+            .height(30)
+            .width(150),
+        )
+        .push(Space::new(15, 10))
+        .push(show_button)
+        .align_items(iced::Alignment::Center);
+    Container::new(
+        Column::new()
+            .push(
+                Text::new("WIF keys are the private keys of the finds in plain text. Anyone who sees them, on screen or in your clipboard, can take the coins.")
+                    .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            )
+            .push(Text::new(format!(
+                "They are shown once and wiped when you are done. Type \"{}\" to show them.",
+                WIF_EXPORT_CONFIRMATION_PHRASE
+            )))
+            .push(Space::new(Length::Fill, 10))
+            .push(buttons),
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(iced::theme::Container::Custom(Box::new(OutputStyles)))
+    .into()
+}
+
+/// The derived keys, prefixed for electrum's import and sweep dialogs.
+pub fn wif_keys_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    if app.wif_keys.is_empty() {
+        return Space::new(0, 0).into();
+    }
+    let mut keys = Column::new();
+    for wif_key in app.wif_keys.iter() {
+        keys = keys.push(Text::new(format!(
+            "{} | {} | {}",
+            wif_key.path,
+            wif_key
+                .script_type
+                .as_ref()
+                .map(script_type_name)
+                .unwrap_or("unknown"),
+            wif_key.address.as_deref().unwrap_or("no address"),
+        )));
+        keys = keys.push(Text::new(wif_key.electrum_key()).font(Font::MONOSPACE));
+        if wif_key
+            .script_type
+            .as_ref()
+            .and_then(electrum_script_type_prefix)
+            .is_none()
+        {
+            keys = keys.push(
+                Text::new("Electrum cannot import this script type.")
+                    .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            );
+        }
+        keys = keys.push(Space::new(10, 5));
+    }
+    let buttons = Row::new()
+        .push(Space::new(Length::Fill, 10))
+        .push(
+            Button::new(text("copy all"))
+                .on_press(AppMessage::CopyWifKeys)
+                .padding([2, 10]),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Button::new(text("done, wipe keys"))
+                .on_press(AppMessage::WipeWifKeys)
+                .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)))
+                .padding([2, 10]),
+        )
+        .align_items(Alignment::Center);
+    Container::new(
+        Column::new()
+            .push(keys)
+            .push(buttons),
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(iced::theme::Container::Custom(Box::new(OutputStyles)))
+    .into()
+}
+
 pub fn results_title_block(_app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
     Text::new("Results")
         .font(Font {
@@ -63,14 +780,14 @@ pub fn results_title_block(_app: &RetrieverApp) -> iced::Element<'_, AppMessage>
 // }
 
 pub fn results_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
-    let text = if app.finds.is_empty() {
-        "None".to_string()
+    let content: iced::Element<'_, AppMessage> = if app.finds.is_empty() {
+        Text::new("None").into()
     } else if !app.finds.is_empty() && app.final_finds.is_empty() {
-        format!("{} ScriptPubkeys derived from you mnemonic was found. To see the details, press `get details` button.", app.finds.len())
+        Text::new(format!("{} ScriptPubkeys derived from you mnemonic was found. To see the details, press `get details` or, without the node, `details from dump`.", app.finds.len())).into()
     } else {
-        format!("{:?}", app.final_finds)
+        results_table_block(app)
     };
-    Container::new(Scrollable::new(Text::new(text)).width(Length::Fill))
+    Container::new(Scrollable::new(content).width(Length::Fill))
         .width(Length::Fill)
         .height(Length::FillPortion(2))
         .align_x(iced::alignment::Horizontal::Left)
@@ -79,6 +796,208 @@ pub fn results_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .into()
 }
 
+const RESULTS_TABLE_COLUMNS: [(ResultsSortColumn, &str, u16); 6] = [
+    (ResultsSortColumn::Index, "#", 1),
+    (ResultsSortColumn::Path, "derivation path", 4),
+    (ResultsSortColumn::ScriptType, "script type", 2),
+    (ResultsSortColumn::Address, "address", 8),
+    (ResultsSortColumn::Amount, "amount (sats)", 3),
+    (ResultsSortColumn::UtxoCount, "utxos", 1),
+];
+
+fn results_table_row<'a>(cells: [String; 6]) -> Row<'a, AppMessage> {
+    cells
+        .into_iter()
+        .zip(RESULTS_TABLE_COLUMNS)
+        .fold(Row::new(), |row, (cell, (_, _, portion))| {
+            row.push(Text::new(cell).width(Length::FillPortion(portion)))
+        })
+        .align_items(Alignment::Center)
+}
+
+pub fn results_table_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let table = &app.results_table;
+    let filters = Row::new()
+        .push(text("script type:"))
+        .push(Space::new(7, 10))
+        .push(
+            PickList::new(
+                ScriptTypeFilter::ALL,
+                Some(table.script_type_filter.clone()),
+                AppMessage::ResultsScriptTypeFilterChanged,
+            )
+            .width(150),
+        )
+        .push(Space::new(15, 10))
+        .push(if table.is_min_amount_sane() {
+            text("minimum amount (sats):")
+        } else {
+            text("minimum amount (sats):").style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR))
+        })
+        .push(Space::new(7, 10))
+        .push(
+            TextInput::new("0", &table.min_amount_input)
+                .on_input(AppMessage::ResultsMinAmountChanged)
+                .width(200),
+        )
+        .align_items(Alignment::Center);
+    let header = RESULTS_TABLE_COLUMNS.iter().fold(Row::new(), |row, (column, title, portion)| {
+        let marker = match (table.sort_column == *column, table.sort_descending) {
+            (true, false) => " ▲",
+            (true, true) => " ▼",
+            (false, _) => "",
+        };
+        row.push(
+            Button::new(text(format!("{}{}", title, marker)).font(Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }))
+            .on_press(AppMessage::ResultsSortRequested(*column))
+            .padding(0)
+            .style(iced::theme::Button::Text)
+            .width(Length::FillPortion(*portion)),
+        )
+    });
+    let rows = table.visible_rows(&app.final_finds);
+    let visible_amount: u64 = rows.iter().map(|final_find| final_find.get_amount_in_sat()).sum();
+    let visible_utxos: usize = rows.iter().map(|final_find| final_find.get_unspents().len()).sum();
+    let total_amount: u64 = app.final_finds.iter().map(|final_find| final_find.get_amount_in_sat()).sum();
+    let mut body = Column::new();
+    for final_find in rows.iter() {
+        body = body.push(find_tree_node(app, final_find));
+    }
+    let footer = results_table_row([
+        "".to_string(),
+        format!("{} of {} finds", rows.len(), app.final_finds.len()),
+        "".to_string(),
+        format!("total of all finds: {} sats", total_amount.to_formatted_string(&Locale::en)),
+        visible_amount.to_formatted_string(&Locale::en),
+        visible_utxos.to_formatted_string(&Locale::en),
+    ]);
+    Column::new()
+        .push(filters)
+        .push(Space::new(Length::Fill, 10))
+        .push(header)
+        .push(Rule::horizontal(5))
+        .push(body)
+        .push(Rule::horizontal(5))
+        .push(footer.padding([0, 10]))
+        .into()
+}
+
+/// A find as a collapsible table row, with its unspents as the leaves.
+pub fn find_tree_node<'a>(
+    app: &'a RetrieverApp,
+    final_find: &'a FinalFinds,
+) -> iced::Element<'a, AppMessage> {
+    let is_expanded = app.expanded_finds.contains(&final_find.get_result_num());
+    let header = Button::new(results_table_row([
+        format!(
+            "{} {}",
+            if is_expanded { "[-]" } else { "[+]" },
+            final_find.get_result_num()
+        ),
+        final_find.get_path().to_string(),
+        match final_find.get_script_type() {
+            Some(script_type) => script_type_name(&script_type).to_string(),
+            None => "unknown".to_string(),
+        },
+        final_find.get_address_string(),
+        final_find.get_amount_in_sat().to_formatted_string(&Locale::en),
+        final_find.get_unspents().len().to_string(),
+    ]))
+    .on_press(AppMessage::ToggleFindExpanded(final_find.get_result_num()))
+    .style(iced::theme::Button::Text);
+    let mut node = Column::new().push(header);
+    if is_expanded {
+        let mut leaves = Column::new()
+            .push(address_block(app, final_find))
+            .push(Text::new(format!("descriptor: {}", final_find.get_descriptor())))
+            .push(
+                Text::new(format!(
+                    "{:<69} {:>20} {:>10} {:>14} {:>9}",
+                    "outpoint", "amount (sats)", "height", "confirmations", "coinbase"
+                ))
+                .font(Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            );
+        for utxo in final_find.get_unspents().iter() {
+            leaves = leaves.push(Text::new(format!(
+                "{:<69} {:>20} {:>10} {:>14} {:>9}",
+                utxo.out_point.to_string(),
+                utxo.amount_in_sat.to_formatted_string(&Locale::en),
+                utxo.height.to_formatted_string(&Locale::en),
+                match final_find
+                    .get_at_height()
+                    .and_then(|at_height| utxo.confirmations(at_height))
+                {
+                    Some(confirmations) => confirmations.to_formatted_string(&Locale::en),
+                    None => "unknown".to_string(),
+                },
+                match utxo.is_coinbase {
+                    Some(true) => "yes",
+                    Some(false) => "no",
+                    None => "unknown",
+                },
+            )));
+        }
+        leaves = leaves.push(Text::new(format!(
+            "{:<69} {:>20}{}",
+            "subtotal",
+            final_find.get_amount_in_sat().to_formatted_string(&Locale::en),
+            match final_find.get_at_height() {
+                Some(at_height) => format!(
+                    "  (confirmations as of height {})",
+                    at_height.to_formatted_string(&Locale::en)
+                ),
+                None => "".to_string(),
+            },
+        )));
+        node = node.push(leaves.padding([0, 0, 10, 25]));
+    }
+    node.into()
+}
+
+pub fn address_block<'a>(
+    app: &'a RetrieverApp,
+    final_find: &'a FinalFinds,
+) -> iced::Element<'a, AppMessage> {
+    let Some(address) = final_find.get_address() else {
+        return Text::new("address: none, p2pk outputs have no address").into();
+    };
+    let is_qr_code_shown = app.shown_qr_codes.contains(&final_find.get_result_num());
+    let address_row = Row::new()
+        .push(Text::new(format!("address: {}", address)))
+        .push(Space::new(10, 10))
+        .push(
+            Button::new(text("copy"))
+                .on_press(AppMessage::CopyToClipboard(address.to_string()))
+                .padding([2, 10]),
+        )
+        .push(Space::new(5, 10))
+        .push(
+            Button::new(text(if is_qr_code_shown { "hide QR" } else { "show QR" }))
+                .on_press(AppMessage::ToggleAddressQrCode(final_find.get_result_num()))
+                .padding([2, 10]),
+        )
+        .push(Space::new(5, 10))
+        .push(
+            Button::new(text("sign message"))
+                .on_press(AppMessage::OpenMessageSigning(final_find.get_result_num()))
+                .padding([2, 10]),
+        )
+        .align_items(Alignment::Center);
+    let mut column = Column::new().push(address_row);
+    if is_qr_code_shown {
+        column = column
+            .push(Space::new(10, 5))
+            .push(address_qr_code_block(&address.to_string()));
+    }
+    column.into()
+}
+
 pub fn errors_title_block(_app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
     Text::new("Errors")
         .font(Font {
@@ -41,6 +41,10 @@ pub fn first_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .push(covered_descriptors_block(app))
         .push(Space::new(15, 10))
         .push(datadir_block(app))
+        .push(Space::new(15, 10))
+        .push(stale_dump_threshold_block(app))
+        .push(Space::new(15, 10))
+        .push(verify_dump_hash_block(app))
         .align_items(Alignment::Center)
         .into()
 }
@@ -163,7 +167,7 @@ pub fn datadir_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
             None,
             "data dir:".to_string(),
             "".to_string(),
-            app.retriever_specific_setting_input.get_gui_data_dir(),
+            &app.retriever_specific_setting_input.get_gui_data_dir(),
             Box::new(|data_dir| {
                 AppMessage::SettingInputInGuiChanged(SettingInputInGuiMessage::DataDirChanged(
                     data_dir,
@@ -175,3 +179,43 @@ pub fn datadir_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .align_items(Alignment::Center)
         .into()
 }
+
+pub fn stale_dump_threshold_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    Row::new()
+        .push(sanity_checked_text_input(
+            app,
+            25,
+            Some(60),
+            "stale dump after (blocks):".to_string(),
+            "".to_string(),
+            &app.retriever_specific_setting_input
+                .get_gui_stale_dump_threshold(),
+            Box::new(|stale_dump_threshold| {
+                AppMessage::SettingInputInGuiChanged(
+                    SettingInputInGuiMessage::StaleDumpThresholdChanged(stale_dump_threshold),
+                )
+            }),
+            app.retriever_specific_setting_input
+                .is_gui_stale_dump_threshold_sane(),
+            app.retriever_specific_setting_input
+                .is_stale_dump_threshold_fixed(),
+        ))
+        .align_items(Alignment::Center)
+        .into()
+}
+
+pub fn verify_dump_hash_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    Row::new()
+        .push(text("verify dump hash:"))
+        .push(Space::new(7, 10))
+        .push(
+            Checkbox::new("", app.retriever_specific_setting_input.get_gui_verify_dump_hash())
+                .on_toggle(|verify_dump_hash| {
+                    AppMessage::SettingInputInGuiChanged(
+                        SettingInputInGuiMessage::VerifyDumpHashChanged(verify_dump_hash),
+                    )
+                }),
+        )
+        .align_items(Alignment::Center)
+        .into()
+}
@@ -1,13 +1,18 @@
 use iced::{
     advanced::widget::Text,
-    widget::{ text, Button, Column, Row, Rule, Space},
+    widget::{text, Button, Column, Container, Row, Rule, Space, TextInput},
     Alignment, Font, Length,
 };
 
+use num_format::{Locale, ToFormattedString};
+
 use crate::{
     app_message::AppMessage,
+    domain::snapshot_header::SUPPORTED_SNAPSHOT_VERSION,
     retriever_styles::{
-        retriever_colors::BITCOIN_ORANGE_COLOR, stop_button_style::StopButtonStyle,
+        outputs_style::OutputStyles,
+        retriever_colors::{BITCOIN_ORANGE_COLOR, INSANE_BACKGROUND_COLOR},
+        stop_button_style::StopButtonStyle,
     },
     RetrieverApp,
 };
@@ -20,10 +25,36 @@ pub fn run_row(
     <RetrieverApp as iced::Application>::Theme,
     iced::Renderer,
 > {
-    Column::new()
+    let mut column = Column::new()
         .push(section_title(app))
         .push(Space::new(Length::Fill, 5))
-        .push(first_row(app))
+        .push(first_row(app));
+    if app.selected_dump.is_some() {
+        column = column
+            .push(Space::new(Length::Fill, 10))
+            .push(dump_metadata_block(app));
+    }
+    if app.retriever_specific_setting_input.is_input_fixed() {
+        column = column
+            .push(Space::new(Length::Fill, 10))
+            .push(dump_manager_block(app));
+    }
+    column = column
+        .push(Space::new(Length::Fill, 10))
+        .push(snapshot_import_block(app))
+        .push(Space::new(Length::Fill, 10))
+        .push(split_workflow_block(app));
+    if app.pending_dump_deletion.is_some() {
+        column = column
+            .push(Space::new(Length::Fill, 10))
+            .push(dump_deletion_block(app));
+    }
+    if app.pending_dump.is_some() {
+        column = column
+            .push(Space::new(Length::Fill, 10))
+            .push(dump_preflight_block(app));
+    }
+    column
         .padding(15)
         .align_items(iced::Alignment::Start)
         .into()
@@ -62,6 +93,8 @@ pub fn first_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .push(Space::new(15, 10))
         .push(get_details_block(app))
         .push(Space::new(15, 10))
+        .push(get_details_from_dump_block(app))
+        .push(Space::new(15, 10))
         .align_items(iced::Alignment::Center)
         .into()
 }
@@ -69,6 +102,7 @@ pub fn first_row(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
 pub fn create_new_dump_file_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
     if app.bitcoincore_client_setting_input.is_input_fixed()
         && app.retriever_specific_setting_input.is_input_fixed()
+        && app.pending_dump.is_none()
     {
         Button::new(
             text("new dump file")
@@ -102,6 +136,7 @@ pub fn create_new_dump_file_block(app: &RetrieverApp) -> iced::Element<'_, AppMe
 pub fn create_or_use_dump_file_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
     if app.bitcoincore_client_setting_input.is_input_fixed()
         && app.retriever_specific_setting_input.is_input_fixed()
+        && app.pending_dump.is_none()
     {
         Button::new(
             text("use/create dump file")
@@ -184,6 +219,7 @@ pub fn search_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         && app.retriever_specific_setting_input.is_input_fixed()
         && !app.is_search_in_progress
         && !app.is_populating_in_progress
+        && app.dump_integrity_problems.is_empty()
     {
         Button::new(
             text("new search").font(Font {
@@ -227,7 +263,10 @@ pub fn search_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
 }
 
 pub fn get_details_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
-    if !app.finds.is_empty() && app.bitcoincore_client_setting_input.is_input_fixed() {
+    if !app.finds.is_empty()
+        && app.bitcoincore_client_setting_input.is_input_fixed()
+        && !app.is_getting_details_in_progress
+    {
         Button::new(
             text("get details").font(Font {
                             weight: iced::font::Weight::Bold,
@@ -254,3 +293,467 @@ pub fn get_details_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
         .into()
     }
 }
+
+pub fn get_details_from_dump_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    if !app.finds.is_empty()
+        && app.selected_dump.is_some()
+        && !app.is_getting_details_in_progress
+        && !app.is_populating_in_progress
+    {
+        Button::new(
+            text("details from dump").font(Font {
+                            weight: iced::font::Weight::Bold,
+                            ..Default::default()
+                        })
+                .vertical_alignment(iced::alignment::Vertical::Center)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+        )
+        .on_press(AppMessage::GetDetailsFromDump)
+        .height(30)
+        .width(Length::FillPortion(1))
+        .into()
+    } else {
+        Button::new(
+            text("details from dump").font(Font {
+                            weight: iced::font::Weight::Bold,
+                            ..Default::default()
+                        })
+                .vertical_alignment(iced::alignment::Vertical::Center)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+        )
+        .height(30)
+        .width(Length::FillPortion(1))
+        .into()
+    }
+}
+
+pub fn dump_metadata_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let Some(selected_dump) = &app.selected_dump else {
+        return Space::new(0, 0).into();
+    };
+    let mut column = Column::new().push(Text::new(format!(
+        "selected dump file: {}",
+        selected_dump.path_string()
+    )));
+    for problem in app.dump_integrity_problems.iter() {
+        column = column.push(
+            Text::new(format!("{} Search is disabled for this dump.", problem))
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+        );
+    }
+    let Some(metadata) = &selected_dump.metadata else {
+        return column.into();
+    };
+    column = column.push(Text::new(format!("dump file | {}", metadata)));
+    if let Some(tip_height) = app.node_tip_height {
+        let threshold = app
+            .retriever_specific_setting_input
+            .get_in_use_stale_dump_threshold();
+        match metadata.blocks_behind(tip_height) {
+            Some(behind) if metadata.is_stale(tip_height, threshold) => {
+                column = column.push(
+                    Text::new(format!(
+                        "This dump is {} blocks behind the node's tip ({}). Coins received since then will not be found. Consider creating a new dump file.",
+                        behind, tip_height
+                    ))
+                    .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+                );
+            }
+            Some(behind) => {
+                column = column.push(Text::new(format!(
+                    "{} blocks behind the node's tip ({}).",
+                    behind, tip_height
+                )));
+            }
+            None => {
+                column = column.push(Text::new(format!(
+                    "Base height of this dump is unknown. Node's tip is at {}.",
+                    tip_height
+                )));
+            }
+        }
+    }
+    column.into()
+}
+
+pub fn dump_manager_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let mut dump_files = Column::new().push(
+        Row::new()
+            .push(
+                Text::new(format!(
+                    "{:<10} {:>12} {:>17} {:>22}  {}",
+                    "chain", "height", "size", "date", "file"
+                ))
+                .font(Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            )
+            .push(Space::new(Length::Fill, 10))
+            .push(
+                Button::new(
+                    text("refresh")
+                        .vertical_alignment(iced::alignment::Vertical::Center)
+                        .horizontal_alignment(iced::alignment::Horizontal::Center),
+                )
+                .on_press(AppMessage::RefreshDumpFiles)
+                .width(150),
+            )
+            .align_items(Alignment::Center),
+    );
+    if app.dump_files.is_empty() {
+        dump_files = dump_files.push(Text::new("No dump files in data dir."));
+    }
+    for entry in app.dump_files.iter() {
+        let is_selected = app
+            .selected_dump
+            .as_ref()
+            .is_some_and(|selected| selected.path == entry.path);
+        let summary = if is_selected {
+            Text::new(entry.summary()).style(iced::theme::Text::Color(BITCOIN_ORANGE_COLOR))
+        } else {
+            Text::new(entry.summary())
+        };
+        let is_idle = !app.is_populating_in_progress && !app.is_search_in_progress;
+        let mut select_button = Button::new(
+            text("select")
+                .vertical_alignment(iced::alignment::Vertical::Center)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+        )
+        .width(70);
+        let mut delete_button = Button::new(
+            text("delete")
+                .vertical_alignment(iced::alignment::Vertical::Center)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+        )
+        .width(70);
+        if is_idle && !is_selected {
+            select_button = select_button.on_press(AppMessage::DumpFileSelected(entry.path.clone()));
+        }
+        if is_idle && app.pending_dump_deletion.is_none() {
+            delete_button = delete_button
+                .on_press(AppMessage::DeleteDumpFileRequested(entry.clone()))
+                .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)));
+        }
+        dump_files = dump_files.push(
+            Row::new()
+                .push(summary)
+                .push(Space::new(Length::Fill, 10))
+                .push(select_button)
+                .push(Space::new(5, 10))
+                .push(delete_button)
+                .align_items(Alignment::Center),
+        );
+    }
+    Container::new(dump_files)
+        .width(Length::Fill)
+        .padding(10)
+        .style(iced::theme::Container::Custom(Box::new(OutputStyles)))
+        .into()
+}
+
+pub fn snapshot_import_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let is_idle = !app.is_populating_in_progress && !app.is_search_in_progress;
+    let mut inspect_button = Button::new(
+        text("inspect")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(150);
+    if is_idle && !app.snapshot_import_path.trim().is_empty() {
+        inspect_button = inspect_button.on_press(AppMessage::InspectSnapshotFile);
+    }
+    let mut column = Column::new().push(
+        Row::new()
+            .push(Text::new("import snapshot file:"))
+            .push(Space::new(7, 10))
+            .push(
+                TextInput::new(
+                    "path to a dumptxoutset output or assumeutxo snapshot",
+                    &app.snapshot_import_path,
+                )
+                .on_input(AppMessage::SnapshotImportPathChanged)
+                .on_submit(AppMessage::InspectSnapshotFile)
+                .width(Length::Fill),
+            )
+            .push(Space::new(7, 10))
+            .push(inspect_button)
+            .align_items(Alignment::Center),
+    );
+    let Some(inspected_snapshot) = &app.inspected_snapshot else {
+        return column.into();
+    };
+    let header = &inspected_snapshot.header;
+    column = column
+        .push(Space::new(Length::Fill, 5))
+        .push(Text::new(format!("{}", header)));
+    let mut warnings = vec![];
+    if header.looks_truncated() {
+        warnings.push("The file is smaller than the coin count in its header allows. It is probably truncated.".to_string());
+    }
+    if let Some(network) = header.network() {
+        if network != app.explorer_setting_input.get_gui_network() {
+            warnings.push(format!(
+                "The snapshot is from {} but the exploration network is {}.",
+                network,
+                app.explorer_setting_input.get_gui_network()
+            ));
+        }
+    }
+    if !header.is_supported() {
+        warnings.push(format!(
+            "This snapshot version can not be populated from. Only version {} snapshots and legacy dumptxoutset files are supported.",
+            SUPPORTED_SNAPSHOT_VERSION
+        ));
+    }
+    for warning in warnings {
+        column = column.push(
+            Text::new(warning).style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+        );
+    }
+    let mut use_button = Button::new(
+        text("populate from this file")
+            .font(Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .height(30)
+    .width(250);
+    if is_idle && header.is_supported() {
+        use_button = use_button.on_press(AppMessage::UseInspectedSnapshot);
+    }
+    column
+        .push(Space::new(Length::Fill, 5))
+        .push(
+            Row::new()
+                .push(Space::new(Length::Fill, 10))
+                .push(
+                    Button::new(
+                        text("dismiss")
+                            .font(Font {
+                                weight: iced::font::Weight::Bold,
+                                ..Default::default()
+                            })
+                            .vertical_alignment(iced::alignment::Vertical::Center)
+                            .horizontal_alignment(iced::alignment::Horizontal::Center),
+                    )
+                    .on_press(AppMessage::DismissInspectedSnapshot)
+                    .height(30)
+                    .width(150),
+                )
+                .push(Space::new(15, 10))
+                .push(use_button)
+                .align_items(Alignment::Center),
+        )
+        .into()
+}
+
+/// Offline derivation writes hashed scripts without the mnemonic's keys, online matching needs only those and the utxo set.
+pub fn split_workflow_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let is_idle = !app.is_populating_in_progress
+        && !app.is_search_in_progress
+        && !app.is_online_match_in_progress;
+    let mut derive_button = Button::new(
+        text("offline derive")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(150);
+    if is_idle
+        && app.explorer_setting_input.is_input_fixed()
+        && app.retriever_specific_setting_input.is_input_fixed()
+    {
+        derive_button = derive_button.on_press(AppMessage::DeriveScriptsOffline);
+    }
+    let mut match_button = Button::new(
+        text("online match")
+            .vertical_alignment(iced::alignment::Vertical::Center)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .width(150);
+    if is_idle
+        && !app.uspk_set.is_empty()
+        && app.retriever_specific_setting_input.is_input_fixed()
+        && !app.derived_scripts_file_input.trim().is_empty()
+    {
+        match_button = match_button.on_press(AppMessage::MatchDerivedScriptsOnline);
+    }
+    let mut column = Column::new().push(
+        Row::new()
+            .push(derive_button)
+            .push(Space::new(15, 10))
+            .push(Text::new("derived scripts file:"))
+            .push(Space::new(7, 10))
+            .push(
+                TextInput::new(
+                    "path to a derived scripts file from an offline machine",
+                    &app.derived_scripts_file_input,
+                )
+                .on_input(AppMessage::DerivedScriptsFilePathChanged)
+                .width(Length::Fill),
+            )
+            .push(Space::new(7, 10))
+            .push(match_button)
+            .align_items(Alignment::Center),
+    );
+    if let Some(summary) = &app.offline_derivation_summary {
+        column = column.push(Text::new(format!(
+            "{} hashed scripts of {} paths written to {}. Move this file to the online machine; it holds no keys.",
+            summary.scripts_written.to_formatted_string(&Locale::en),
+            summary.paths_derived.to_formatted_string(&Locale::en),
+            summary.file_path.to_string_lossy()
+        )));
+    }
+    if app.is_online_match_in_progress {
+        column = column.push(Text::new("Matching the derived scripts against the unspent scripts."));
+    }
+    if let Some(report) = &app.online_match_report {
+        if report.network != app.explorer_setting_input.get_gui_network() {
+            column = column.push(
+                Text::new(format!(
+                    "The scripts were derived for {} but the exploration network is {}.",
+                    report.network,
+                    app.explorer_setting_input.get_gui_network()
+                ))
+                .style(iced::theme::Text::Color(INSANE_BACKGROUND_COLOR)),
+            );
+        }
+        column = column.push(Text::new(format!(
+            "{} of {} derived scripts are unspent. Matches saved to {}.",
+            report.matches.len().to_formatted_string(&Locale::en),
+            report.scripts_in_file.to_formatted_string(&Locale::en),
+            report.matches_file.to_string_lossy()
+        )));
+        for online_match in report.matches.iter() {
+            column = column.push(Text::new(format!(
+                "{} | {} | {}",
+                online_match.path,
+                online_match.script_type,
+                online_match
+                    .address
+                    .as_deref()
+                    .unwrap_or(&online_match.script_pubkey)
+            )));
+        }
+    }
+    column.into()
+}
+
+pub fn dump_deletion_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let Some(entry) = &app.pending_dump_deletion else {
+        return Space::new(0, 0).into();
+    };
+    let buttons = Row::new()
+        .push(Space::new(Length::Fill, 10))
+        .push(
+            Button::new(
+                text("cancel")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::DeleteDumpFileDismissed)
+            .height(30)
+            .width(150),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Button::new(
+                text("delete")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::DeleteDumpFileConfirmed)
+            .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)))
+            .height(30)
+            .width(150),
+        )
+        .align_items(iced::Alignment::Center);
+    Container::new(
+        Column::new()
+            .push(Text::new(format!(
+                "Delete {} and its metadata? This cannot be undone.",
+                entry.path_string()
+            )))
+            .push(Space::new(Length::Fill, 10))
+            .push(buttons),
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(iced::theme::Container::Custom(Box::new(OutputStyles)))
+    .into()
+}
+
+pub fn dump_preflight_block(app: &RetrieverApp) -> iced::Element<'_, AppMessage> {
+    let Some(pending_dump) = &app.pending_dump else {
+        return Space::new(0, 0).into();
+    };
+    let report = &pending_dump.report;
+    let mut problems = Column::new().push(
+        Text::new(format!(
+            "bitcoincore ({}) at block {} of {} headers. The following problems were found before dumping the utxo set:",
+            report.chain, report.blocks, report.headers
+        ))
+        .font(Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        }),
+    );
+    for problem in report.problems() {
+        problems = problems
+            .push(Space::new(Length::Fill, 5))
+            .push(Text::new(format!("- {}", problem)));
+    }
+    let buttons = Row::new()
+        .push(Space::new(Length::Fill, 10))
+        .push(
+            Button::new(
+                text("cancel")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::DumpPreflightDismissed)
+            .height(30)
+            .width(150),
+        )
+        .push(Space::new(15, 10))
+        .push(
+            Button::new(
+                text("dump anyway")
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .on_press(AppMessage::DumpPreflightConfirmed)
+            .style(iced::theme::Button::Custom(Box::new(StopButtonStyle)))
+            .height(30)
+            .width(150),
+        )
+        .align_items(iced::Alignment::Center);
+    Container::new(
+        problems
+            .push(Space::new(Length::Fill, 10))
+            .push(buttons),
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(iced::theme::Container::Custom(Box::new(OutputStyles)))
+    .into()
+}